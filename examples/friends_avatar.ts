@@ -0,0 +1,17 @@
+import { writeFileSync } from 'fs';
+import * as steamworks from '../index.js';
+
+async function main() {
+  const steam = new steamworks.SteamClient();
+  const user = steam.getCurrentUser();
+
+  const avatar = await steam.getAvatar(user.steamId, steamworks.AvatarSizeEnum.Large, true);
+  if (!avatar) {
+    console.log('No avatar set');
+    return;
+  }
+  console.log(`Avatar is ${avatar.width}x${avatar.height}`);
+  writeFileSync('avatar.png', avatar.png!);
+}
+
+main().catch(console.error);
@@ -7,11 +7,18 @@ async function main() {
 
   // Display friends
   for (const friend of friends.slice(0, 10)) { // Show first 10 friends
-    console.log(`- ${friend.name} (${friend.steamId})`)
-    console.log(`  State: ${friend.state}`)
+    const nickname = friend.nickname ? ` "${friend.nickname}"` : ''
+    console.log(`- ${friend.name}${nickname} (${friend.steamId}), level ${friend.steamLevel}`)
+    console.log(`  State: ${friend.state}, relationship: ${friend.relationship}`)
 
     if (friend.gamePlayed) {
-      console.log(`  Currently playing: ${friend.gamePlayed}`)
+      console.log(`  Currently playing: ${friend.gamePlayed.appId}`)
+      if (friend.gamePlayed.lobbySteamId) {
+        console.log(`  In lobby: ${friend.gamePlayed.lobbySteamId}`)
+      }
+      if (friend.gamePlayed.gameServerIp) {
+        console.log(`  On server: ${friend.gamePlayed.gameServerIp}:${friend.gamePlayed.gameServerPort}`)
+      }
     }
 
     // Try to get rich presence of friend
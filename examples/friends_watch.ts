@@ -0,0 +1,16 @@
+import * as steamworks from '../index.js';
+
+async function main() {
+  const steam = new steamworks.SteamClient();
+  steam.onPersonaStateChange(event => {
+    const changed = Object.entries(event)
+      .filter(([key, value]) => value === true)
+      .map(([key]) => key);
+    console.log(`${steam.getFriendPersonaName(event.steamId)} changed: ${changed.join(', ')}`);
+  });
+
+  // Listeners don't keep the process alive, so wait around for a while
+  await new Promise(resolve => setTimeout(resolve, 60_000));
+}
+
+main().catch(console.error);
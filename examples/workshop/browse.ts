@@ -0,0 +1,23 @@
+import * as steamworks from '../../index.js';
+
+async function main() {
+  const steam = new steamworks.SteamClient();
+  const page = await steam.workshop.queryAll(
+    steamworks.WorkshopQueryType.RankedByTrend,
+    steamworks.WorkshopUGCType.Items,
+    2212330, // yomi hustle
+    null,
+    1,
+    {
+      searchText: process.argv[2],
+      requiredTags: process.argv.slice(3),
+      matchAnyTag: true,
+    }
+  );
+  console.log(`Showing ${page.returnedResults} of ${page.totalResults} items`);
+  for (const item of page.items) {
+    console.log(`${item.itemId}: ${item.title} (+${item.numUpvotes}/-${item.numDownvotes})`);
+  }
+}
+
+main().catch(console.error);
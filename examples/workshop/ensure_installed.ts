@@ -0,0 +1,13 @@
+import * as steamworks from '../../index.js';
+
+async function main() {
+  const steam = new steamworks.SteamClient();
+  const id = process.argv.length === 3 ? Number(process.argv[2]) : 3422500423;
+  const info = await steam.workshop.ensureInstalled(id, {
+    timeoutMs: 5 * 60_000,
+    onProgress: ({ current, total }) => console.log(`${current}/${total} bytes`),
+  });
+  console.log('Installed to:', info.folder);
+}
+
+main().catch(console.error);
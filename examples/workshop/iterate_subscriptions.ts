@@ -0,0 +1,18 @@
+import * as steamworks from '../../index.js';
+
+async function main() {
+  const steam = new steamworks.SteamClient();
+  const user = steam.getCurrentUser();
+  const items = steam.workshop.iterateUser(
+    2212330, // yomi hustle
+    user.accountId,
+    steamworks.WorkshopUserListType.Subscribed,
+    steamworks.WorkshopUGCType.Items,
+    steamworks.WorkshopUserListOrder.SubscriptionDateDesc
+  );
+  for await (const item of items) {
+    console.log(`${item.itemId}: ${item.title}`);
+  }
+}
+
+main().catch(console.error);
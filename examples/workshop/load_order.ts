@@ -0,0 +1,17 @@
+import * as steamworks from '../../index.js'
+
+async function main() {
+  const steam = new steamworks.SteamClient()
+  const subscriptions = steam.workshop.getSubscriptions()
+
+  const { order, cycles, missing } = await steam.workshop.resolveLoadOrder(subscriptions)
+  console.log('Load order:', order)
+  for (const cycle of cycles) {
+    console.warn('Dependency cycle:', cycle.join(' -> '))
+  }
+  for (const dependency of missing) {
+    console.warn(`Missing ${dependency.itemId}, required by`, dependency.requiredBy)
+  }
+}
+
+main().catch(console.error)
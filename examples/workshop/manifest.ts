@@ -0,0 +1,24 @@
+import { existsSync, readFileSync, writeFileSync } from 'fs'
+import * as steamworks from '../../index.js'
+
+const MANIFEST_PATH = 'workshop-manifest.json'
+
+async function main() {
+  const steam = new steamworks.SteamClient()
+
+  if (!existsSync(MANIFEST_PATH)) {
+    const manifest = steam.workshop.getSubscriptionManifest()
+    writeFileSync(MANIFEST_PATH, JSON.stringify(manifest, null, 2))
+    console.log(`Saved ${manifest.items.length} items to ${MANIFEST_PATH}`)
+    return
+  }
+
+  const diff = steam.workshop.diffSubscriptionManifest(readFileSync(MANIFEST_PATH, 'utf8'))
+  console.log('Added:', diff.added.map(item => item.itemId))
+  console.log('Removed:', diff.removed)
+  console.log('Updated:', diff.updated.map(item => item.itemId))
+  console.log('Broken:', diff.broken.map(item => item.itemId))
+  writeFileSync(MANIFEST_PATH, JSON.stringify(diff.manifest, null, 2))
+}
+
+main().catch(console.error)
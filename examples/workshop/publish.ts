@@ -0,0 +1,41 @@
+import { command, run, positional, option, string, optional } from 'cmd-ts'
+import * as steamworks from '../../index.js'
+
+const publishCmd = command({
+  name: 'publish',
+  args: {
+    content: positional({
+      displayName: 'content_folder',
+      description: 'Folder to upload as the item content'
+    }),
+    title: option({ type: string, long: 'title', description: 'Item title' }),
+    preview: option({ type: optional(string), long: 'preview', description: 'Preview image path' }),
+    changelog: option({ type: optional(string), long: 'changelog', description: 'Change note' }),
+  },
+  async handler({ content, title, preview, changelog }) {
+    const steam = new steamworks.SteamClient()
+    const appId = 480
+
+    const created = await steam.workshop.createItem(appId, steamworks.WorkshopFileType.Community)
+    console.log('Created item:', created.itemId)
+    if (created.needsToAcceptAgreement) {
+      console.log('You need to accept the workshop legal agreement before the item becomes visible.')
+    }
+
+    const update = steam.workshop.startItemUpdate(created.itemId, {
+      title,
+      contentPath: content,
+      previewPath: preview,
+      changelog,
+      visibility: steamworks.WorkshopItemVisibility.Private,
+    })
+    update.onProgress(({ status, bytesProcessed, bytesTotal }) => {
+      console.log(`status ${status}: ${bytesProcessed}/${bytesTotal} bytes`)
+    }, 500)
+
+    const result = await update.wait()
+    console.log('Published item:', result.itemId)
+  }
+})
+
+run(publishCmd, process.argv.slice(2))
@@ -0,0 +1,19 @@
+import * as steamworks from '../../index.js';
+
+async function main() {
+  const steam = new steamworks.SteamClient();
+  steam.workshop.onDownloadItemResult(({ itemId, result }) => {
+    console.log(`Download of ${itemId} finished with result ${result}`);
+  });
+  steam.workshop.onItemInstalled(({ itemId }) => {
+    console.log(`Item ${itemId} installed`);
+  });
+
+  const id = process.argv.length === 3 ? Number(process.argv[2]) : 3422500423;
+  steam.workshop.downloadItem(id, true);
+
+  // Listeners don't keep the process alive, so wait around for a while
+  await new Promise(resolve => setTimeout(resolve, 60_000));
+}
+
+main().catch(console.error);
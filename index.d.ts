@@ -22,6 +22,73 @@ export interface WorkshopItemDetails {
   numDownvotes: number
   score: number
   numChildren: number
+  previewUrl?: string
+  metadata?: string
+  keyValueTags?: Array<WorkshopKeyValueTag>
+  additionalPreviews?: Array<WorkshopItemPreview>
+  children?: Array<number>
+  statistics?: WorkshopItemStatistics
+}
+export const enum WorkshopItemPreviewType {
+  Image = 0,
+  YouTubeVideo = 1,
+  Sketchfab = 2,
+  EnvironmentMapHorizontalCross = 3,
+  EnvironmentMapLatLong = 4,
+  Clip = 5
+}
+export interface WorkshopItemPreview {
+  index: number
+  previewType: WorkshopItemPreviewType
+  urlOrVideoId: string
+  originalFileName?: string
+}
+export interface WorkshopItemStatistics {
+  subscriptions?: number
+  favorites?: number
+  followers?: number
+  uniqueSubscriptions?: number
+  uniqueFavorites?: number
+  uniqueFollowers?: number
+  uniqueWebsiteViews?: number
+  secondsPlayed?: number
+  playtimeSessions?: number
+  comments?: number
+}
+export interface WorkshopItemPage {
+  items: Array<WorkshopItemDetails>
+  totalResults: number
+  returnedResults: number
+  nextCursor?: number
+}
+export interface WorkshopItemIteratorResult {
+  done: boolean
+  value?: WorkshopItemDetails
+}
+export interface WorkshopItemLookup {
+  itemId: number
+  details?: WorkshopItemDetails
+  error?: string
+}
+export interface WorkshopKeyValueTag {
+  key: string
+  value: string
+}
+export interface WorkshopQueryOptions {
+  requiredTags?: Array<string>
+  matchAnyTag?: boolean
+  excludedTags?: Array<string>
+  requiredKeyValueTags?: Array<WorkshopKeyValueTag>
+  searchText?: string
+  rankedByTrendDays?: number
+  language?: string
+  cacheMaxAgeSeconds?: number
+  includeMetadata?: boolean
+  includeKeyValueTags?: boolean
+  includeAdditionalPreviews?: boolean
+  includeChildren?: boolean
+  includeStatistics?: boolean
+  playtimeStatsDays?: number
 }
 export declare function itemStateToString(state: WorkshopItemStateEnum): string
 export const enum WorkshopItemVisibility {
@@ -112,7 +179,17 @@ export const enum WorkshopItemStateEnum {
   Installed = 4,
   NeedsUpdate = 8,
   Downloading = 16,
-  DownloadPending = 32
+  DownloadPending = 32,
+  DisabledLocally = 64
+}
+export interface ItemStateFlags {
+  subscribed: boolean
+  legacy: boolean
+  installed: boolean
+  needsUpdate: boolean
+  downloading: boolean
+  downloadPending: boolean
+  disabledLocally: boolean
 }
 export interface WorkshopItemUpdateDetails {
   title?: string
@@ -121,12 +198,47 @@ export interface WorkshopItemUpdateDetails {
   previewPath?: string
   contentPath?: string
   tags?: Array<string>
-  visibility?: number
+  visibility?: WorkshopItemVisibility
+  addPreviewFiles?: Array<string>
+  addPreviewVideos?: Array<string>
+  replacePreviews?: Array<WorkshopPreviewReplacement>
+  removePreviews?: Array<number>
+  metadata?: string
+  addKeyValueTags?: Array<WorkshopKeyValueTag>
+  removeKeyValueTags?: Array<string>
+}
+export interface WorkshopPreviewReplacement {
+  index: number
+  file?: string
+  videoId?: string
 }
 export interface WorkshopItemCreationResult {
   itemId: number
   needsToAcceptAgreement: boolean
 }
+export interface WorkshopLoadOrder {
+  order: Array<number>
+  cycles: Array<Array<number>>
+  missing: Array<WorkshopMissingDependency>
+}
+export interface WorkshopMissingDependency {
+  itemId: number
+  requiredBy: Array<number>
+}
+export interface WorkshopOverlayResult {
+  overlayEnabled: boolean
+  url: string
+}
+export interface WorkshopItemVote {
+  votedUp: boolean
+  votedDown: boolean
+  voteSkipped: boolean
+}
+export interface WorkshopItemEvent {
+  appId: number
+  itemId: number
+  result: number
+}
 export interface WorkshopItemInstallInfo {
   folder: string
   sizeOnDisk: number
@@ -136,12 +248,56 @@ export interface DownloadInfo {
   current: number
   total: number
 }
+export const enum WorkshopItemUpdateStatus {
+  Invalid = 0,
+  PreparingConfig = 1,
+  PreparingContent = 2,
+  UploadingContent = 3,
+  UploadingPreviewFile = 4,
+  CommittingChanges = 5
+}
+export interface WorkshopItemUpdateProgress {
+  status: WorkshopItemUpdateStatus
+  bytesProcessed: number
+  bytesTotal: number
+}
+export interface EnsureInstalledOptions {
+  highPriority?: boolean
+  timeoutMs?: number
+  onProgress?: (value: DownloadInfo) => any
+}
+export interface WorkshopManifestItem {
+  itemId: number
+  states: Array<WorkshopItemStateEnum>
+  folder?: string
+  sizeOnDisk?: number
+  timestamp?: number
+}
+export interface WorkshopSubscriptionManifest {
+  items: Array<WorkshopManifestItem>
+}
+export interface WorkshopManifestDiff {
+  added: Array<WorkshopManifestItem>
+  removed: Array<number>
+  updated: Array<WorkshopManifestItem>
+  broken: Array<WorkshopManifestItem>
+  manifest: WorkshopSubscriptionManifest
+}
 export interface FriendInfo {
   steamId: number
   name: string
-  state: number
-  relationship: number
-  gamePlayed?: string
+  nickname?: string
+  state: PersonaStateEnum
+  relationship: FriendRelationshipEnum
+  steamLevel: number
+  gamePlayed?: FriendGameInfo
+}
+export interface FriendGameInfo {
+  appId: number
+  gameServerIp?: string
+  gameServerPort: number
+  queryPort: number
+  lobbySteamId?: number
 }
 export const enum FriendRelationshipEnum {
   None = 'None',
@@ -162,10 +318,40 @@ export const enum PersonaStateEnum {
   LookingToPlay = 'LookingToPlay',
   Invisible = 'Invisible'
 }
+export const enum AvatarSizeEnum {
+  Small = 'Small',
+  Medium = 'Medium',
+  Large = 'Large'
+}
+export interface FriendAvatar {
+  width: number
+  height: number
+  rgba: Buffer
+  png?: Buffer
+}
 export interface RichPresenceInfo {
   key: string
   value: string
 }
+export interface FriendRichPresenceEvent {
+  steamId: number
+  appId: number
+}
+export interface PersonaStateChangeEvent {
+  steamId: number
+  name: boolean
+  status: boolean
+  cameOnline: boolean
+  wentOffline: boolean
+  gamePlayed: boolean
+  gameServer: boolean
+  avatar: boolean
+  relationship: boolean
+  nickname: boolean
+  steamLevel: boolean
+  richPresence: boolean
+  changeFlags: number
+}
 export interface UserInfo {
   steamId: number
   accountId: number
@@ -174,18 +360,60 @@ export interface UserInfo {
   level: number
   loggedOn: boolean
 }
+export declare class WorkshopItemIterator {
+  next(): Promise<IteratorResult<WorkshopItemDetails, undefined>>
+}
+export declare class WorkshopItemUpdate {
+  getProgress(): WorkshopItemUpdateProgress
+  isFinished(): boolean
+  onProgress(callback: (value: WorkshopItemUpdateProgress) => any, intervalMs?: number | undefined | null): void
+  wait(): Promise<WorkshopItemCreationResult>
+}
 export type UGCClient = UgcClient
 export declare class UgcClient {
   itemStateToString(state: WorkshopItemStateEnum): string
-  queryUser(appId: number, account: number, listType: WorkshopUserListType, itemType: WorkshopUGCType, sortOrder: WorkshopUserListOrder, page: number): Promise<Array<WorkshopItemDetails> | null>
-  getItem(itemId: number): Promise<WorkshopItemDetails | null>
+  queryUser(appId: number, account: number, listType: WorkshopUserListType, itemType: WorkshopUGCType, sortOrder: WorkshopUserListOrder, page: number, options?: WorkshopQueryOptions | undefined | null): Promise<WorkshopItemPage>
+  queryAll(queryType: WorkshopQueryType, itemType: WorkshopUGCType, creatorAppId: number, consumerAppId: number | undefined | null, page: number, options?: WorkshopQueryOptions | undefined | null): Promise<WorkshopItemPage>
+  iterateUser(appId: number, account: number, listType: WorkshopUserListType, itemType: WorkshopUGCType, sortOrder: WorkshopUserListOrder, options?: WorkshopQueryOptions | undefined | null): AsyncIterable<WorkshopItemDetails>
+  iterateAll(queryType: WorkshopQueryType, itemType: WorkshopUGCType, creatorAppId: number, consumerAppId?: number | undefined | null, options?: WorkshopQueryOptions | undefined | null): AsyncIterable<WorkshopItemDetails>
+  getItem(itemId: number, options?: WorkshopQueryOptions | undefined | null): Promise<WorkshopItemDetails | null>
+  getItems(itemIds: Array<number>, options?: WorkshopQueryOptions | undefined | null): Promise<Array<WorkshopItemLookup>>
+  resolveLoadOrder(itemIds: Array<number>): Promise<WorkshopLoadOrder>
   subscribeToItem(itemId: number): Promise<void>
   unsubscribeToItem(itemId: number): Promise<void>
+  deleteItem(itemId: number): Promise<void>
+  setItemVote(itemId: number, voteUp: boolean): Promise<void>
+  getItemVote(itemId: number): Promise<WorkshopItemVote>
+  addItemToFavorites(itemId: number): Promise<void>
+  removeItemFromFavorites(itemId: number): Promise<void>
+  addItemChild(parentId: number, childId: number): Promise<void>
+  removeItemChild(parentId: number, childId: number): Promise<void>
+  addAppDependency(itemId: number, appId: number): Promise<void>
+  removeAppDependency(itemId: number, appId: number): Promise<void>
+  getAppDependencies(itemId: number): Promise<Array<number>>
+  createItem(appId: number, fileType: WorkshopFileType): Promise<WorkshopItemCreationResult>
+  submitItemUpdate(itemId: number, details: WorkshopItemUpdateDetails): Promise<WorkshopItemCreationResult>
+  startItemUpdate(itemId: number, details: WorkshopItemUpdateDetails): WorkshopItemUpdate
+  onItemInstalled(callback: (value: WorkshopItemEvent) => any): number
+  onDownloadItemResult(callback: (value: WorkshopItemEvent) => any): number
+  removeListener(listenerId: number): boolean
+  removeAllListeners(): void
   getSubscriptions(): Array<number>
+  getSubscriptionManifest(): WorkshopSubscriptionManifest
+  diffSubscriptionManifest(previousJson: string): WorkshopManifestDiff
   getInstallInfo(itemId: number): WorkshopItemInstallInfo | null
   getDownloadInfo(itemId: number): DownloadInfo | null
   downloadItem(itemId: number, highPriority: boolean): boolean
-  getItemState(itemId: number): number
+  ensureInstalled(itemId: number, options?: EnsureInstalledOptions | undefined | null): Promise<WorkshopItemInstallInfo>
+  showLegalAgreement(): WorkshopOverlayResult
+  showItemPage(itemId: number): WorkshopOverlayResult
+  suspendDownloads(suspend: boolean): void
+  whileDownloadsSuspended<T>(promise: Promise<T>): Promise<T>
+  startPlaytimeTracking(itemIds: Array<number>): void
+  stopPlaytimeTracking(itemIds: Array<number>): void
+  stopPlaytimeTrackingForAllItems(): void
+  getItemState(itemId: number): ItemStateFlags
+  getItemStateBulk(itemIds: Array<number>): Array<ItemStateFlags>
   getItemStates(itemId: number): Array<WorkshopItemStateEnum>
 }
 export declare class SteamClient {
@@ -198,8 +426,8 @@ export declare class SteamClient {
   getFriendPersonaName(steamId: number): string
   getFriendPersonaState(steamId: number): PersonaStateEnum
   getFriendRelationship(steamId: number): FriendRelationshipEnum
-  getFriendGamePlayed(steamId: number): string | null
-  setPersonaName(name: string): void
+  getFriendGamePlayed(steamId: number): FriendGameInfo | null
+  setPersonaName(name: string): Promise<void>
   setPersonaState(state: PersonaStateEnum): void
   getPersonaName(): string
   getPersonaState(): PersonaStateEnum
@@ -213,5 +441,12 @@ export declare class SteamClient {
   getFriendRichPresence(steamId: number, key: string): string | null
   getRichPresenceKeys(): Array<string>
   getAllRichPresence(): Array<RichPresenceInfo>
+  getAllFriendRichPresence(steamId: number): Array<RichPresenceInfo>
+  requestFriendRichPresence(steamId: number): void
+  onRichPresenceUpdated(callback: (value: FriendRichPresenceEvent) => any): number
+  onPersonaStateChange(callback: (value: PersonaStateChangeEvent) => any): number
+  removeFriendsListener(listenerId: number): boolean
+  removeAllFriendsListeners(): void
+  getAvatar(steamId: number, size: AvatarSizeEnum, png?: boolean | undefined | null): Promise<FriendAvatar | null>
   inviteUserToGame(steamId: number, connectString: string): boolean
 }
@@ -310,9 +310,10 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { itemStateToString, WorkshopItemVisibility, WorkshopFileType, WorkshopQueryType, WorkshopUGCType, WorkshopUserListType, WorkshopUserListOrder, WorkshopItemStateEnum, UgcClient, FriendRelationshipEnum, PersonaStateEnum, SteamClient } = nativeBinding
+const { itemStateToString, WorkshopItemPreviewType, WorkshopItemVisibility, WorkshopFileType, WorkshopQueryType, WorkshopUGCType, WorkshopUserListType, WorkshopUserListOrder, WorkshopItemStateEnum, WorkshopItemUpdateStatus, WorkshopItemIterator, WorkshopItemUpdate, UgcClient, FriendRelationshipEnum, PersonaStateEnum, AvatarSizeEnum, SteamClient } = nativeBinding
 
 module.exports.itemStateToString = itemStateToString
+module.exports.WorkshopItemPreviewType = WorkshopItemPreviewType
 module.exports.WorkshopItemVisibility = WorkshopItemVisibility
 module.exports.WorkshopFileType = WorkshopFileType
 module.exports.WorkshopQueryType = WorkshopQueryType
@@ -320,7 +321,11 @@ module.exports.WorkshopUGCType = WorkshopUGCType
 module.exports.WorkshopUserListType = WorkshopUserListType
 module.exports.WorkshopUserListOrder = WorkshopUserListOrder
 module.exports.WorkshopItemStateEnum = WorkshopItemStateEnum
+module.exports.WorkshopItemUpdateStatus = WorkshopItemUpdateStatus
+module.exports.WorkshopItemIterator = WorkshopItemIterator
+module.exports.WorkshopItemUpdate = WorkshopItemUpdate
 module.exports.UgcClient = UgcClient
 module.exports.FriendRelationshipEnum = FriendRelationshipEnum
 module.exports.PersonaStateEnum = PersonaStateEnum
+module.exports.AvatarSizeEnum = AvatarSizeEnum
 module.exports.SteamClient = SteamClient
@@ -25,3 +25,11 @@ pub fn steam_error_to_napi(err: steamworks::SteamError) -> napi::Error {
 pub fn create_query_error_to_napi(err: CreateQueryError) -> napi::Error {
     napi::Error::from_reason(err.to_string())
 }
+
+// For operations Steam doesn't let games perform, instead of pretending they succeeded
+pub fn unsupported_to_napi(operation: &str) -> napi::Error {
+    napi::Error::from_reason(format!(
+        "Steamworks: {} is not supported, Steam doesn't expose it to games",
+        operation
+    ))
+}
@@ -0,0 +1,113 @@
+use napi::bindgen_prelude::*;
+use napi::{Error, Result};
+use steamworks::{Client, SteamId};
+use steamworks_sys as sys;
+use tokio::time::{timeout_at, Duration, Instant};
+
+use super::events::FriendsEvents;
+use super::{AvatarSizeEnum, FriendAvatar};
+use crate::steam_api;
+
+const AVATAR_TIMEOUT: Duration = Duration::from_secs(10);
+
+// GetLargeFriendAvatar returns this while the image is still being downloaded
+const AVATAR_LOADING: i32 = -1;
+
+fn avatar_image(steam_id: u64, size: AvatarSizeEnum) -> i32 {
+  let friends = steam_api::friends();
+  unsafe {
+    match size {
+      AvatarSizeEnum::Small => sys::SteamAPI_ISteamFriends_GetSmallFriendAvatar(friends, steam_id),
+      AvatarSizeEnum::Medium => sys::SteamAPI_ISteamFriends_GetMediumFriendAvatar(friends, steam_id),
+      AvatarSizeEnum::Large => sys::SteamAPI_ISteamFriends_GetLargeFriendAvatar(friends, steam_id),
+    }
+  }
+}
+
+fn read_image(image: i32) -> Result<(u32, u32, Vec<u8>)> {
+  let utils = steam_api::utils();
+  let mut width = 0u32;
+  let mut height = 0u32;
+  if !unsafe { sys::SteamAPI_ISteamUtils_GetImageSize(utils, image, &mut width, &mut height) } {
+    return Err(Error::from_reason("Steamworks: Failed to get avatar size"));
+  }
+
+  let mut rgba = vec![0u8; (width * height * 4) as usize];
+  if !unsafe { sys::SteamAPI_ISteamUtils_GetImageRGBA(utils, image, rgba.as_mut_ptr(), rgba.len() as i32) } {
+    return Err(Error::from_reason("Steamworks: Failed to read avatar image"));
+  }
+  Ok((width, height, rgba))
+}
+
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>> {
+  let to_napi = |e: png::EncodingError| Error::from_reason(format!("Steamworks: Failed to encode avatar: {}", e));
+
+  let mut png = Vec::new();
+  let mut encoder = png::Encoder::new(&mut png, width, height);
+  encoder.set_color(png::ColorType::Rgba);
+  encoder.set_depth(png::BitDepth::Eight);
+  let mut writer = encoder.write_header().map_err(to_napi)?;
+  writer.write_image_data(rgba).map_err(to_napi)?;
+  writer.finish().map_err(to_napi)?;
+  Ok(png)
+}
+
+/// Resolves with a user's avatar, waiting for Steam to download it if it isn't cached yet.
+/// Resolves with `None` if the user has no avatar.
+pub async fn get_avatar(
+  client: &Client,
+  events: &FriendsEvents,
+  steam_id: f64,
+  size: AvatarSizeEnum,
+  encode_as_png: bool,
+) -> Result<Option<FriendAvatar>> {
+  let steam_id = steam_id as u64;
+
+  // Listening before asking for anything so an answer can't slip past
+  let (waiters, mut changes) = events.avatar_changes(client, steam_id);
+
+  // Steam only has avatars of users it has persona data for. Until then the small and medium
+  // sizes come back as 0, the same as for a user without an avatar, so ask for the data first.
+  let user = SteamId::from_raw(steam_id);
+  let mut requested = client.friends().request_user_information(user, false);
+  let mut image = avatar_image(steam_id, size);
+
+  let deadline = Instant::now() + AVATAR_TIMEOUT;
+  while requested || image == AVATAR_LOADING {
+    match timeout_at(deadline, changes.recv()).await {
+      Ok(Some(())) => {}
+      Ok(None) => {
+        return Err(Error::from_reason(format!(
+          "Steamworks: Stopped receiving avatar updates for {}",
+          steam_id
+        )))
+      }
+      Err(_) => {
+        return Err(Error::from_reason(format!(
+          "Steamworks: Timed out waiting for the avatar of {}",
+          steam_id
+        )))
+      }
+    }
+    // False once Steam has everything it was asked for
+    requested = client.friends().request_user_information(user, false);
+    image = avatar_image(steam_id, size);
+  }
+  drop(waiters);
+  if image == 0 {
+    return Ok(None);
+  }
+
+  let (width, height, rgba) = read_image(image)?;
+  let png = if encode_as_png {
+    Some(encode_png(width, height, &rgba)?.into())
+  } else {
+    None
+  };
+  Ok(Some(FriendAvatar {
+    width,
+    height,
+    rgba: rgba.into(),
+    png,
+  }))
+}
@@ -0,0 +1,140 @@
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction};
+use std::ffi::c_void;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU32, Ordering};
+use steamworks::{Callback, Client};
+use steamworks_sys as sys;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use super::{FriendRichPresenceEvent, PersonaStateChangeEvent};
+use crate::listeners::{ListenerSet, Waiter};
+
+// FriendRichPresenceUpdate_t, posted when a friend's rich presence changes or a
+// RequestFriendRichPresence call has been answered
+pub struct FriendRichPresenceUpdate {
+  pub steam_id: u64,
+  pub app_id: u32,
+}
+
+unsafe impl Callback for FriendRichPresenceUpdate {
+  const ID: i32 = 336;
+  const SIZE: i32 = size_of::<sys::FriendRichPresenceUpdate_t>() as i32;
+
+  unsafe fn from_raw(raw: *mut c_void) -> Self {
+    let val = &mut *(raw as *mut sys::FriendRichPresenceUpdate_t);
+    FriendRichPresenceUpdate {
+      steam_id: val.m_steamIDFriend.m_steamid.m_unAll64Bits,
+      app_id: val.m_nAppID,
+    }
+  }
+}
+
+// PersonaStateChange_t, posted when anything about a user we know of changes.
+// steamworks-rs has its own version of this, but its flags don't cover rich presence.
+pub struct PersonaStateChange {
+  pub steam_id: u64,
+  pub change_flags: u32,
+}
+
+unsafe impl Callback for PersonaStateChange {
+  const ID: i32 = 304;
+  const SIZE: i32 = size_of::<sys::PersonaStateChange_t>() as i32;
+
+  unsafe fn from_raw(raw: *mut c_void) -> Self {
+    let val = &mut *(raw as *mut sys::PersonaStateChange_t);
+    PersonaStateChange {
+      steam_id: val.m_ulSteamID,
+      change_flags: val.m_nChangeFlags as u32,
+    }
+  }
+}
+
+// AvatarImageLoaded_t, posted when an avatar that wasn't cached has finished downloading
+pub struct AvatarImageLoaded {
+  pub steam_id: u64,
+}
+
+unsafe impl Callback for AvatarImageLoaded {
+  const ID: i32 = 334;
+  const SIZE: i32 = size_of::<sys::AvatarImageLoaded_t>() as i32;
+
+  unsafe fn from_raw(raw: *mut c_void) -> Self {
+    let val = &mut *(raw as *mut sys::AvatarImageLoaded_t);
+    AvatarImageLoaded {
+      steam_id: val.m_steamID.m_steamid.m_unAll64Bits,
+    }
+  }
+}
+
+pub type FriendRichPresenceCallback = ThreadsafeFunction<FriendRichPresenceEvent, ErrorStrategy::Fatal>;
+pub type PersonaStateChangeCallback = ThreadsafeFunction<PersonaStateChangeEvent, ErrorStrategy::Fatal>;
+
+pub type AvatarWaiters<'a> = (Waiter<'a, PersonaStateChange>, Waiter<'a, AvatarImageLoaded>);
+
+/// Listeners for friends callbacks
+#[derive(Default)]
+pub struct FriendsEvents {
+  next_id: AtomicU32,
+  rich_presence_updated: ListenerSet<FriendRichPresenceUpdate>,
+  persona_state_change: ListenerSet<PersonaStateChange>,
+  avatar_image_loaded: ListenerSet<AvatarImageLoaded>,
+}
+
+impl FriendsEvents {
+  pub fn on_rich_presence_updated(&self, client: &Client, callback: FriendRichPresenceCallback) -> u32 {
+    let listener_id = self.next_id();
+    self.rich_presence_updated.add(
+      client,
+      listener_id,
+      callback,
+      |event: &FriendRichPresenceUpdate| FriendRichPresenceEvent {
+        steam_id: event.steam_id as f64,
+        app_id: event.app_id,
+      },
+    );
+    listener_id
+  }
+
+  pub fn on_persona_state_change(&self, client: &Client, callback: PersonaStateChangeCallback) -> u32 {
+    let listener_id = self.next_id();
+    self.persona_state_change.add(client, listener_id, callback, |event: &PersonaStateChange| {
+      PersonaStateChangeEvent::from_raw(event.steam_id, event.change_flags)
+    });
+    listener_id
+  }
+
+  /// Wakes the receiver whenever Steam has news about `steam_id` or has finished downloading
+  /// one of their avatars, for as long as the waiters are alive
+  pub fn avatar_changes(&self, client: &Client, steam_id: u64) -> (AvatarWaiters<'_>, UnboundedReceiver<()>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let persona_tx = tx.clone();
+    let persona_waiter = self
+      .persona_state_change
+      .wait(client, self.next_id(), move |event: &PersonaStateChange| {
+        if event.steam_id == steam_id {
+          let _ = persona_tx.send(());
+        }
+      });
+    let image_waiter = self
+      .avatar_image_loaded
+      .wait(client, self.next_id(), move |event: &AvatarImageLoaded| {
+        if event.steam_id == steam_id {
+          let _ = tx.send(());
+        }
+      });
+    ((persona_waiter, image_waiter), rx)
+  }
+
+  pub fn remove(&self, listener_id: u32) -> bool {
+    self.rich_presence_updated.remove(listener_id) || self.persona_state_change.remove(listener_id)
+  }
+
+  pub fn remove_all(&self) {
+    self.rich_presence_updated.clear();
+    self.persona_state_change.clear();
+  }
+
+  fn next_id(&self) -> u32 {
+    self.next_id.fetch_add(1, Ordering::Relaxed)
+  }
+}
@@ -0,0 +1,539 @@
+mod avatar;
+mod events;
+
+use std::ffi::{CStr, CString};
+use std::sync::mpsc;
+use std::sync::Arc;
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use steamworks::{FriendFlags, Friend};
+use steamworks_sys as sys;
+
+use crate::errors::{steam_error_to_napi, unsupported_to_napi};
+use crate::steam_api;
+
+pub use events::{FriendRichPresenceCallback, PersonaStateChangeCallback};
+use events::FriendsEvents;
+
+#[napi(object)]
+pub struct FriendInfo {
+    pub steam_id: f64,
+    pub name: String,
+    pub nickname: Option<String>, // The nickname the current user gave this friend, if any
+    pub state: PersonaStateEnum,
+    pub relationship: FriendRelationshipEnum,
+    pub steam_level: u32,         // 0 until Steam has loaded it
+    pub game_played: Option<FriendGameInfo>,
+}
+
+#[napi(object)]
+pub struct FriendGameInfo {
+    pub app_id: u32,
+    pub game_server_ip: Option<String>, // Only set while on a game server
+    pub game_server_port: u32,
+    pub query_port: u32,
+    pub lobby_steam_id: Option<f64>,    // Only set while in a lobby
+}
+
+#[napi(string_enum)]
+pub enum FriendRelationshipEnum {
+    None,
+    Blocked,
+    RequestRecipient,
+    Friend,
+    RequestInitiator,
+    Ignored,
+    IgnoredFriend,
+}
+
+#[napi(string_enum)]
+pub enum PersonaStateEnum {
+    Offline,
+    Online,
+    Busy,
+    Away,
+    Snooze,
+    LookingToTrade,
+    LookingToPlay,
+    Invisible,
+}
+
+#[napi(string_enum)]
+pub enum AvatarSizeEnum {
+    Small,  // 32x32
+    Medium, // 64x64
+    Large,  // 184x184
+}
+
+#[napi(object)]
+pub struct FriendAvatar {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Buffer,
+    pub png: Option<Buffer>, // Only set when a PNG was asked for
+}
+
+#[napi(object)]
+pub struct RichPresenceInfo {
+    pub key: String,
+    pub value: String,
+}
+
+// Posted when a friend's rich presence has changed or been fetched
+#[napi(object)]
+pub struct FriendRichPresenceEvent {
+    pub steam_id: f64,
+    pub app_id: u32,
+}
+
+// EPersonaChange flags
+const PERSONA_CHANGE_NAME: u32 = 0x0001;
+const PERSONA_CHANGE_STATUS: u32 = 0x0002;
+const PERSONA_CHANGE_COME_ONLINE: u32 = 0x0004;
+const PERSONA_CHANGE_GONE_OFFLINE: u32 = 0x0008;
+const PERSONA_CHANGE_GAME_PLAYED: u32 = 0x0010;
+const PERSONA_CHANGE_GAME_SERVER: u32 = 0x0020;
+const PERSONA_CHANGE_AVATAR: u32 = 0x0040;
+const PERSONA_CHANGE_RELATIONSHIP_CHANGED: u32 = 0x0200;
+const PERSONA_CHANGE_NICKNAME: u32 = 0x1000;
+const PERSONA_CHANGE_STEAM_LEVEL: u32 = 0x2000;
+const PERSONA_CHANGE_RICH_PRESENCE: u32 = 0x4000;
+
+// Posted when something about a user changed; each flag says whether that part changed
+#[napi(object)]
+pub struct PersonaStateChangeEvent {
+    pub steam_id: f64,
+    pub name: bool,
+    pub status: bool,
+    pub came_online: bool,
+    pub went_offline: bool,
+    pub game_played: bool,
+    pub game_server: bool,
+    pub avatar: bool,
+    pub relationship: bool, // Friend added, removed, blocked or a request came in
+    pub nickname: bool,
+    pub steam_level: bool,
+    pub rich_presence: bool,
+    pub change_flags: u32,  // The raw EPersonaChange bits, including ones not listed above
+}
+
+impl PersonaStateChangeEvent {
+    pub fn from_raw(steam_id: u64, change_flags: u32) -> Self {
+        let has = |flag: u32| change_flags & flag != 0;
+        PersonaStateChangeEvent {
+            steam_id: steam_id as f64,
+            name: has(PERSONA_CHANGE_NAME),
+            status: has(PERSONA_CHANGE_STATUS),
+            came_online: has(PERSONA_CHANGE_COME_ONLINE),
+            went_offline: has(PERSONA_CHANGE_GONE_OFFLINE),
+            game_played: has(PERSONA_CHANGE_GAME_PLAYED),
+            game_server: has(PERSONA_CHANGE_GAME_SERVER),
+            avatar: has(PERSONA_CHANGE_AVATAR),
+            relationship: has(PERSONA_CHANGE_RELATIONSHIP_CHANGED),
+            nickname: has(PERSONA_CHANGE_NICKNAME),
+            steam_level: has(PERSONA_CHANGE_STEAM_LEVEL),
+            rich_presence: has(PERSONA_CHANGE_RICH_PRESENCE),
+            change_flags,
+        }
+    }
+}
+
+pub struct FriendsClient {
+    client: Arc<steamworks::Client>,
+    events: FriendsEvents,
+}
+
+impl FriendsClient {
+    pub fn new(client: Arc<steamworks::Client>) -> Self {
+        FriendsClient {
+            client,
+            events: FriendsEvents::default(),
+        }
+    }
+
+    pub fn get_friend_count(&self, flags: Option<Vec<FriendRelationshipEnum>>) -> Result<u32> {
+        let friends = self.client.friends();
+        
+        let flags = match flags {
+            Some(enum_flags) => {
+                let mut friend_flags = FriendFlags::empty();
+                for flag in enum_flags {
+                    match flag {
+                        FriendRelationshipEnum::None => {},
+                        FriendRelationshipEnum::Blocked => friend_flags |= FriendFlags::BLOCKED,
+                        FriendRelationshipEnum::RequestRecipient => friend_flags |= FriendFlags::FRIENDSHIP_REQUESTED,
+                        FriendRelationshipEnum::Friend => friend_flags |= FriendFlags::IMMEDIATE,
+                        FriendRelationshipEnum::RequestInitiator => friend_flags |= FriendFlags::REQUESTING_FRIENDSHIP,
+                        FriendRelationshipEnum::Ignored => friend_flags |= FriendFlags::IGNORED,
+                        FriendRelationshipEnum::IgnoredFriend => friend_flags |= FriendFlags::IGNORED_FRIEND,
+                    }
+                }
+                friend_flags
+            },
+            None => FriendFlags::IMMEDIATE, // Default to immediate friends
+        };
+        
+        // There's no get_friend_count in the API, we'll use get_friends().len() instead
+        let count = friends.get_friends(flags).len();
+        Ok(count as u32)
+    }
+
+    pub fn get_friends(&self, flags: Option<Vec<FriendRelationshipEnum>>) -> Result<Vec<FriendInfo>> {
+        let friends = self.client.friends();
+        
+        let flags = match flags {
+            Some(enum_flags) => {
+                let mut friend_flags = FriendFlags::empty();
+                for flag in enum_flags {
+                    match flag {
+                        FriendRelationshipEnum::None => {},
+                        FriendRelationshipEnum::Blocked => friend_flags |= FriendFlags::BLOCKED,
+                        FriendRelationshipEnum::RequestRecipient => friend_flags |= FriendFlags::FRIENDSHIP_REQUESTED,
+                        FriendRelationshipEnum::Friend => friend_flags |= FriendFlags::IMMEDIATE,
+                        FriendRelationshipEnum::RequestInitiator => friend_flags |= FriendFlags::REQUESTING_FRIENDSHIP,
+                        FriendRelationshipEnum::Ignored => friend_flags |= FriendFlags::IGNORED,
+                        FriendRelationshipEnum::IgnoredFriend => friend_flags |= FriendFlags::IGNORED_FRIEND,
+                    }
+                }
+                friend_flags
+            },
+            None => FriendFlags::IMMEDIATE, // Default to immediate friends
+        };
+        
+        let friend_list = friends
+            .get_friends(flags)
+            .iter()
+            .map(|friend| self.to_friend_info(friend))
+            .collect();
+        
+        Ok(friend_list)
+    }
+    
+    pub fn get_friend_persona_name(&self, steam_id: f64) -> Result<String> {
+        let friends = self.client.friends();
+        let steam_id = steamworks::SteamId::from_raw(steam_id as u64);
+        let friend = friends.get_friend(steam_id);
+        Ok(friend.name())
+    }
+    
+    pub fn get_friend_persona_state(&self, steam_id: f64) -> Result<PersonaStateEnum> {
+        let friends = self.client.friends();
+        let steam_id = steamworks::SteamId::from_raw(steam_id as u64);
+        let friend = friends.get_friend(steam_id);
+        Ok(self.to_persona_state_enum(friend.state()))
+    }
+    
+    pub fn get_friend_relationship(&self, steam_id: f64) -> Result<FriendRelationshipEnum> {
+        let friends = self.client.friends();
+        let steam_id = steamworks::SteamId::from_raw(steam_id as u64);
+        let friend = friends.get_friend(steam_id);
+        Ok(self.get_friend_relationship_enum(&friend))
+    }
+    
+    pub fn get_friend_game_played(&self, steam_id: f64) -> Result<Option<FriendGameInfo>> {
+        let friends = self.client.friends();
+        let steam_id = steamworks::SteamId::from_raw(steam_id as u64);
+        let friend = friends.get_friend(steam_id);
+        Ok(friend.game_played().map(to_friend_game_info))
+    }
+    
+    pub fn set_persona_name(&self, name: String) -> AsyncTask<SetPersonaNameTask> {
+        AsyncTask::new(SetPersonaNameTask { name })
+    }
+    
+    pub fn set_persona_state(&self, _state: PersonaStateEnum) -> Result<()> {
+        // Only the Steam client itself can change the user's status
+        Err(unsupported_to_napi("Setting the persona state"))
+    }
+    
+    pub fn get_persona_name(&self) -> Result<String> {
+        let friends = self.client.friends();
+        Ok(friends.name())
+    }
+    
+    pub fn get_persona_state(&self) -> Result<PersonaStateEnum> {
+        // steamworks-rs only exposes the state of other users
+        let state = unsafe { sys::SteamAPI_ISteamFriends_GetPersonaState(steam_api::friends()) };
+        Ok(persona_state_from_raw(state))
+    }
+    
+    pub async fn add_friend(&self, _steam_id: f64) -> Result<bool> {
+        // Games can only open the overlay's friend request dialog, not send requests themselves
+        Err(unsupported_to_napi("Adding a friend"))
+    }
+    
+    pub fn remove_friend(&self, _steam_id: f64) -> Result<bool> {
+        Err(unsupported_to_napi("Removing a friend"))
+    }
+    
+    pub fn has_friend(&self, steam_id: f64, flags: Option<Vec<FriendRelationshipEnum>>) -> Result<bool> {
+        let friends = self.client.friends();
+        let steam_id = steamworks::SteamId::from_raw(steam_id as u64);
+        let friend = friends.get_friend(steam_id);
+        
+        let flags = match flags {
+            Some(enum_flags) => {
+                let mut friend_flags = FriendFlags::empty();
+                for flag in enum_flags {
+                    match flag {
+                        FriendRelationshipEnum::None => {},
+                        FriendRelationshipEnum::Blocked => friend_flags |= FriendFlags::BLOCKED,
+                        FriendRelationshipEnum::RequestRecipient => friend_flags |= FriendFlags::FRIENDSHIP_REQUESTED,
+                        FriendRelationshipEnum::Friend => friend_flags |= FriendFlags::IMMEDIATE,
+                        FriendRelationshipEnum::RequestInitiator => friend_flags |= FriendFlags::REQUESTING_FRIENDSHIP,
+                        FriendRelationshipEnum::Ignored => friend_flags |= FriendFlags::IGNORED,
+                        FriendRelationshipEnum::IgnoredFriend => friend_flags |= FriendFlags::IGNORED_FRIEND,
+                    }
+                }
+                friend_flags
+            },
+            None => FriendFlags::IMMEDIATE, // Default to immediate friends
+        };
+        
+        Ok(friend.has_friend(flags))
+    }
+    
+    pub fn request_user_information(&self, steam_id: f64, name_only: bool) -> Result<bool> {
+        let friends = self.client.friends();
+        let steam_id = steamworks::SteamId::from_raw(steam_id as u64);
+        Ok(friends.request_user_information(steam_id, name_only))
+    }
+    
+    pub fn set_rich_presence(&self, key: String, value: String) -> Result<bool> {
+        let friends = self.client.friends();
+        Ok(friends.set_rich_presence(&key, Some(&value)))
+    }
+    
+    pub fn clear_rich_presence(&self) -> Result<()> {
+        let friends = self.client.friends();
+        friends.clear_rich_presence();
+        Ok(())
+    }
+    
+    pub fn get_rich_presence(&self, key: String) -> Result<Option<String>> {
+        friend_rich_presence(self.own_steam_id(), &key)
+    }
+    
+    pub fn get_friend_rich_presence(&self, steam_id: f64, key: String) -> Result<Option<String>> {
+        friend_rich_presence(steam_id as u64, &key)
+    }
+    
+    pub fn get_rich_presence_keys(&self) -> Result<Vec<String>> {
+        Ok(friend_rich_presence_keys(self.own_steam_id()))
+    }
+    
+    pub fn get_all_rich_presence(&self) -> Result<Vec<RichPresenceInfo>> {
+        self.get_all_friend_rich_presence(self.own_steam_id() as f64)
+    }
+    
+    pub fn get_all_friend_rich_presence(&self, steam_id: f64) -> Result<Vec<RichPresenceInfo>> {
+        let steam_id = steam_id as u64;
+        let mut presence = Vec::new();
+        for key in friend_rich_presence_keys(steam_id) {
+            if let Some(value) = friend_rich_presence(steam_id, &key)? {
+                presence.push(RichPresenceInfo { key, value });
+            }
+        }
+        Ok(presence)
+    }
+    
+    // Steam only keeps rich presence for friends in the same game, anyone else has to be
+    // requested first. The values are available once on_rich_presence_updated fires.
+    pub fn request_friend_rich_presence(&self, steam_id: f64) -> Result<()> {
+        unsafe { sys::SteamAPI_ISteamFriends_RequestFriendRichPresence(steam_api::friends(), steam_id as u64) };
+        Ok(())
+    }
+    
+    pub async fn get_avatar(&self, steam_id: f64, size: AvatarSizeEnum, png: Option<bool>) -> Result<Option<FriendAvatar>> {
+        avatar::get_avatar(&self.client, &self.events, steam_id, size, png.unwrap_or(false)).await
+    }
+    
+    pub fn on_rich_presence_updated(&self, callback: FriendRichPresenceCallback) -> u32 {
+        self.events.on_rich_presence_updated(&self.client, callback)
+    }
+    
+    pub fn on_persona_state_change(&self, callback: PersonaStateChangeCallback) -> u32 {
+        self.events.on_persona_state_change(&self.client, callback)
+    }
+    
+    pub fn remove_listener(&self, listener_id: u32) -> bool {
+        self.events.remove(listener_id)
+    }
+    
+    pub fn remove_all_listeners(&self) {
+        self.events.remove_all()
+    }
+    
+    pub fn invite_user_to_game(&self, steam_id: f64, connect_string: String) -> Result<bool> {
+        let friends = self.client.friends();
+        let steam_id = steamworks::SteamId::from_raw(steam_id as u64);
+        let friend = friends.get_friend(steam_id);
+        friend.invite_user_to_game(&connect_string);
+        Ok(true)
+    }
+    
+    fn own_steam_id(&self) -> u64 {
+        self.client.user().steam_id().raw()
+    }
+    
+    fn to_friend_info(&self, friend: &Friend) -> FriendInfo {
+        let friends = steam_api::friends();
+        let steam_id = friend.id().raw();
+        // Neither the nickname nor the level are wrapped by steamworks-rs
+        let nickname = unsafe { sys::SteamAPI_ISteamFriends_GetPlayerNickname(friends, steam_id) };
+        let nickname = if nickname.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(nickname) }.to_string_lossy().into_owned())
+        };
+        let steam_level = unsafe { sys::SteamAPI_ISteamFriends_GetFriendSteamLevel(friends, steam_id) };
+        
+        FriendInfo {
+            steam_id: steam_id as f64,
+            name: friend.name(),
+            nickname,
+            state: self.to_persona_state_enum(friend.state()),
+            relationship: self.get_friend_relationship_enum(friend),
+            steam_level: steam_level.max(0) as u32,
+            game_played: friend.game_played().map(to_friend_game_info),
+        }
+    }
+    
+    // Helper function to convert steamworks::FriendState to PersonaStateEnum
+    fn to_persona_state_enum(&self, state: steamworks::FriendState) -> PersonaStateEnum {
+        match state {
+            steamworks::FriendState::Offline => PersonaStateEnum::Offline,
+            steamworks::FriendState::Online => PersonaStateEnum::Online,
+            steamworks::FriendState::Busy => PersonaStateEnum::Busy,
+            steamworks::FriendState::Away => PersonaStateEnum::Away,
+            steamworks::FriendState::Snooze => PersonaStateEnum::Snooze,
+            steamworks::FriendState::LookingToTrade => PersonaStateEnum::LookingToTrade,
+            steamworks::FriendState::LookingToPlay => PersonaStateEnum::LookingToPlay,
+        }
+    }
+    
+    // Helper function to determine Friend's relationship enum
+    fn get_friend_relationship_enum(&self, friend: &Friend) -> FriendRelationshipEnum {
+        if friend.has_friend(FriendFlags::BLOCKED) {
+            return FriendRelationshipEnum::Blocked;
+        }
+        if friend.has_friend(FriendFlags::FRIENDSHIP_REQUESTED) {
+            return FriendRelationshipEnum::RequestRecipient;
+        }
+        if friend.has_friend(FriendFlags::IMMEDIATE) {
+            return FriendRelationshipEnum::Friend;
+        }
+        if friend.has_friend(FriendFlags::REQUESTING_FRIENDSHIP) {
+            return FriendRelationshipEnum::RequestInitiator;
+        }
+        if friend.has_friend(FriendFlags::IGNORED) {
+            return FriendRelationshipEnum::Ignored;
+        }
+        if friend.has_friend(FriendFlags::IGNORED_FRIEND) {
+            return FriendRelationshipEnum::IgnoredFriend;
+        }
+        FriendRelationshipEnum::None
+    }
+}
+
+fn to_friend_game_info(game: steamworks::FriendGame) -> FriendGameInfo {
+    let lobby_steam_id = game.lobby.raw();
+    FriendGameInfo {
+        app_id: game.game.app_id().0,
+        game_server_ip: if game.game_address.is_unspecified() {
+            None
+        } else {
+            Some(game.game_address.to_string())
+        },
+        game_server_port: game.game_port as u32,
+        query_port: game.query_port as u32,
+        lobby_steam_id: if lobby_steam_id == 0 { None } else { Some(lobby_steam_id as f64) },
+    }
+}
+
+// Rich presence is read through the flat API, steamworks-rs can only set it
+fn friend_rich_presence(steam_id: u64, key: &str) -> Result<Option<String>> {
+    let key = CString::new(key)
+        .map_err(|_| Error::from_reason("Steamworks: Rich presence key must not contain NUL bytes"))?;
+    let value = unsafe {
+        CStr::from_ptr(sys::SteamAPI_ISteamFriends_GetFriendRichPresence(steam_api::friends(), steam_id, key.as_ptr()))
+    }
+    .to_string_lossy()
+    .into_owned();
+    // Steam returns an empty string for keys that aren't set
+    Ok(if value.is_empty() { None } else { Some(value) })
+}
+
+fn friend_rich_presence_keys(steam_id: u64) -> Vec<String> {
+    let friends = steam_api::friends();
+    let count = unsafe { sys::SteamAPI_ISteamFriends_GetFriendRichPresenceKeyCount(friends, steam_id) };
+    (0..count)
+        .map(|index| {
+            unsafe { CStr::from_ptr(sys::SteamAPI_ISteamFriends_GetFriendRichPresenceKeyByIndex(friends, steam_id, index)) }
+                .to_string_lossy()
+                .into_owned()
+        })
+        .filter(|key| !key.is_empty())
+        .collect()
+}
+
+// Converts a raw EPersonaState, which unlike steamworks::FriendState includes Invisible
+fn persona_state_from_raw(state: sys::EPersonaState) -> PersonaStateEnum {
+    match state {
+        sys::EPersonaState::k_EPersonaStateOnline => PersonaStateEnum::Online,
+        sys::EPersonaState::k_EPersonaStateBusy => PersonaStateEnum::Busy,
+        sys::EPersonaState::k_EPersonaStateAway => PersonaStateEnum::Away,
+        sys::EPersonaState::k_EPersonaStateSnooze => PersonaStateEnum::Snooze,
+        sys::EPersonaState::k_EPersonaStateLookingToTrade => PersonaStateEnum::LookingToTrade,
+        sys::EPersonaState::k_EPersonaStateLookingToPlay => PersonaStateEnum::LookingToPlay,
+        sys::EPersonaState::k_EPersonaStateInvisible => PersonaStateEnum::Invisible,
+        _ => PersonaStateEnum::Offline,
+    }
+}
+
+/// Async Task for changing the current user's persona name
+pub struct SetPersonaNameTask {
+    name: String,
+}
+
+#[napi]
+impl Task for SetPersonaNameTask {
+    type Output = ();
+    type JsValue = ();
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let name = CString::new(self.name.as_str())
+            .map_err(|_| Error::from_reason("Steamworks: Persona name must not contain NUL bytes"))?;
+        let (tx, rx) = mpsc::channel();
+        let call = unsafe { sys::SteamAPI_ISteamFriends_SetPersonaName(steam_api::call_friends(), name.as_ptr()) };
+        steam_api::register_call_result::<sys::SetPersonaNameResponse_t>(call, move |result| {
+            let _ = tx.send(result);
+        });
+
+        let response = match rx.recv() {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => return Err(Error::from_reason(format!("Steamworks: Failed to set persona name: {}", e))),
+            Err(e) => {
+                return Err(Error::from_reason(format!(
+                    r"Steamworks: Failed to receive persona name result: {:?}",
+                    e
+                )))
+            }
+        };
+        if response.m_bSuccess {
+            Ok(())
+        } else {
+            steam_api::check_result(response.m_result).map_err(steam_error_to_napi)?;
+            Err(Error::from_reason("Steamworks: Failed to set persona name"))
+        }
+    }
+
+    fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+
+    fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+        Err(error)
+    }
+}
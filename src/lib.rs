@@ -6,8 +6,11 @@ extern crate napi_derive;
 mod ugc;
 mod friends;
 mod errors;
+mod listeners;
+mod steam_api;
 
 use napi::bindgen_prelude::*;
+use napi::Env;
 use steamworks::{Client, SteamId};
 use ugc::*;
 use friends::*;
@@ -37,6 +40,8 @@ impl SteamClient {
   pub fn new() -> Result<Self> {
     match Client::init() {
       Ok(_client) => {
+        steam_api::init_call_results()
+          .map_err(|e| Error::from_reason(format!("Failed to init: {}", e)))?;
         let client = Arc::new(_client);
         let ugc = Arc::new(UGCClient::new(client.clone()));
         let friends = FriendsClient::new(client.clone());
@@ -54,13 +59,14 @@ impl SteamClient {
       loop {
         intv.tick().await;
         client.run_callbacks();
+        steam_api::run_call_results();
       }
     });
   }
 
   #[napi(getter)]
   pub fn workshop(&self) -> UGCClient {
-    return UGCClient::new(self.client.clone());
+    return (*self.ugc).clone();
   }
 
   // Get information about the current user
@@ -139,17 +145,17 @@ impl SteamClient {
 
   // Get the game played by a friend
   #[napi]
-  pub fn get_friend_game_played(&self, steam_id: f64) -> Result<Option<String>> {
+  pub fn get_friend_game_played(&self, steam_id: f64) -> Result<Option<FriendGameInfo>> {
     self.friends.get_friend_game_played(steam_id)
   }
 
   // Set the persona name
   #[napi]
-  pub fn set_persona_name(&self, name: String) -> Result<()> {
+  pub fn set_persona_name(&self, name: String) -> AsyncTask<SetPersonaNameTask> {
     self.friends.set_persona_name(name)
   }
 
-  // Set the persona state
+  // Set the persona state. Always throws, Steam doesn't let games change it
   #[napi]
   pub fn set_persona_state(&self, state: PersonaStateEnum) -> Result<()> {
     self.friends.set_persona_state(state)
@@ -167,13 +173,13 @@ impl SteamClient {
     self.friends.get_persona_state()
   }
 
-  // Add a friend
+  // Add a friend. Always throws, Steam doesn't let games send friend requests
   #[napi]
   pub async fn add_friend(&self, steam_id: f64) -> Result<bool> {
     self.friends.add_friend(steam_id).await
   }
 
-  // Remove a friend
+  // Remove a friend. Always throws, Steam doesn't let games remove friends
   #[napi]
   pub fn remove_friend(&self, steam_id: f64) -> Result<bool> {
     self.friends.remove_friend(steam_id)
@@ -227,6 +233,52 @@ impl SteamClient {
     self.friends.get_all_rich_presence()
   }
 
+  // Get all rich presence values of a friend
+  #[napi]
+  pub fn get_all_friend_rich_presence(&self, steam_id: f64) -> Result<Vec<RichPresenceInfo>> {
+    self.friends.get_all_friend_rich_presence(steam_id)
+  }
+
+  // Ask Steam for a friend's rich presence, e.g. when they aren't in the same game
+  #[napi]
+  pub fn request_friend_rich_presence(&self, steam_id: f64) -> Result<()> {
+    self.friends.request_friend_rich_presence(steam_id)
+  }
+
+  // Calls `callback` whenever a friend's rich presence changes or a request for it completes
+  #[napi]
+  pub fn on_rich_presence_updated(&self, env: Env, mut callback: FriendRichPresenceCallback) -> Result<u32> {
+    // Listeners shouldn't keep the Node process alive on their own
+    callback.unref(&env)?;
+    Ok(self.friends.on_rich_presence_updated(callback))
+  }
+
+  // Calls `callback` whenever a friend's name, status, game, avatar, nickname or rich presence
+  // changes, and when someone is added to or removed from the friends list
+  #[napi]
+  pub fn on_persona_state_change(&self, env: Env, mut callback: PersonaStateChangeCallback) -> Result<u32> {
+    callback.unref(&env)?;
+    Ok(self.friends.on_persona_state_change(callback))
+  }
+
+  // Remove a friends listener
+  #[napi]
+  pub fn remove_friends_listener(&self, listener_id: u32) -> bool {
+    self.friends.remove_listener(listener_id)
+  }
+
+  // Remove every friends listener
+  #[napi]
+  pub fn remove_all_friends_listeners(&self) {
+    self.friends.remove_all_listeners()
+  }
+
+  // Get a user's avatar as RGBA pixels, and optionally as a PNG. Resolves with null if they have none
+  #[napi]
+  pub async fn get_avatar(&self, steam_id: f64, size: AvatarSizeEnum, png: Option<bool>) -> Result<Option<FriendAvatar>> {
+    self.friends.get_avatar(steam_id, size, png).await
+  }
+
   // Invite user to game
   #[napi]
   pub fn invite_user_to_game(&self, steam_id: f64, connect_string: String) -> Result<bool> {
@@ -236,5 +288,7 @@ impl SteamClient {
 
 impl Drop for SteamClient {
   fn drop(&mut self) {
+    // Don't leave workshop playtime sessions open
+    self.client.ugc().stop_playtime_tracking_for_all_items();
   }
 }
@@ -0,0 +1,125 @@
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use steamworks::{Callback, CallbackHandle, Client};
+
+type Listener<C> = Box<dyn Fn(&C) + Send>;
+
+struct Listeners<C> {
+  // Added from JS, and removed by `clear`
+  js: HashMap<u32, Listener<C>>,
+  // Added from Rust for the length of an operation, e.g. waiting for a download
+  waiters: HashMap<u32, Listener<C>>,
+}
+
+impl<C> Listeners<C> {
+  fn is_empty(&self) -> bool {
+    self.js.is_empty() && self.waiters.is_empty()
+  }
+}
+
+/// Everything listening for one Steam callback.
+///
+/// steamworks-rs keeps a single handler per callback id and registering another replaces it,
+/// so every listener shares one registration that is made for the first listener and dropped
+/// with the last.
+pub struct ListenerSet<C: 'static> {
+  listeners: Arc<Mutex<Listeners<C>>>,
+  // Also serializes add/remove, so the registration always matches whether there are listeners
+  handle: Mutex<Option<CallbackHandle>>,
+}
+
+impl<C: 'static> Default for ListenerSet<C> {
+  fn default() -> Self {
+    ListenerSet {
+      listeners: Arc::new(Mutex::new(Listeners {
+        js: HashMap::new(),
+        waiters: HashMap::new(),
+      })),
+      handle: Mutex::new(None),
+    }
+  }
+}
+
+impl<C: Callback> ListenerSet<C> {
+  /// Adds `callback` under `listener_id`, calling it with `to_event(&event)` for every `C` Steam posts
+  pub fn add<T: 'static>(
+    &self,
+    client: &Client,
+    listener_id: u32,
+    callback: ThreadsafeFunction<T, ErrorStrategy::Fatal>,
+    to_event: fn(&C) -> T,
+  ) {
+    self.insert(client, |listeners| {
+      listeners.js.insert(
+        listener_id,
+        Box::new(move |event: &C| {
+          callback.call(to_event(event), ThreadsafeFunctionCallMode::NonBlocking);
+        }),
+      );
+    });
+  }
+
+  /// Calls `waiter` for every `C` Steam posts until the returned guard is dropped
+  pub fn wait(&self, client: &Client, listener_id: u32, waiter: impl Fn(&C) + Send + 'static) -> Waiter<'_, C> {
+    self.insert(client, |listeners| {
+      listeners.waiters.insert(listener_id, Box::new(waiter));
+    });
+    Waiter {
+      set: self,
+      listener_id,
+    }
+  }
+
+  fn insert(&self, client: &Client, insert: impl FnOnce(&mut Listeners<C>)) {
+    let mut handle = self.handle.lock().unwrap();
+    insert(&mut self.listeners.lock().unwrap());
+
+    if handle.is_none() {
+      let listeners = self.listeners.clone();
+      *handle = Some(client.register_callback(move |event: C| {
+        let listeners = listeners.lock().unwrap();
+        for listener in listeners.js.values().chain(listeners.waiters.values()) {
+          listener(&event);
+        }
+      }));
+    }
+  }
+
+  pub fn remove(&self, listener_id: u32) -> bool {
+    self.remove_where(|listeners| {
+      listeners.js.remove(&listener_id).is_some() || listeners.waiters.remove(&listener_id).is_some()
+    })
+  }
+
+  /// Removes every listener added from JS, waiters stay until they are done
+  pub fn clear(&self) {
+    self.remove_where(|listeners| listeners.js.clear());
+  }
+
+  fn remove_where<R>(&self, remove: impl FnOnce(&mut Listeners<C>) -> R) -> R {
+    let mut handle = self.handle.lock().unwrap();
+    let (removed, now_empty) = {
+      let mut listeners = self.listeners.lock().unwrap();
+      let removed = remove(&mut listeners);
+      (removed, listeners.is_empty())
+    };
+    if now_empty {
+      // Dropping the handle unregisters the Steam callback
+      *handle = None;
+    }
+    removed
+  }
+}
+
+/// Removes a waiter added with `ListenerSet::wait` once the wait is over, however it ended
+pub struct Waiter<'a, C: Callback> {
+  set: &'a ListenerSet<C>,
+  listener_id: u32,
+}
+
+impl<C: Callback> Drop for Waiter<'_, C> {
+  fn drop(&mut self) {
+    self.set.remove(self.listener_id);
+  }
+}
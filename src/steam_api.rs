@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CStr};
+use std::mem::{size_of, MaybeUninit};
+use std::sync::{Mutex, OnceLock};
+use steamworks_sys as sys;
+
+// Raw access to the flat Steamworks API for the parts steamworks-rs doesn't wrap.
+// The interface versions have to match the SDK steamworks-sys was generated from.
+
+pub fn ugc() -> *mut sys::ISteamUGC {
+  unsafe { sys::SteamAPI_SteamUGC_v018() }
+}
+
+pub fn friends() -> *mut sys::ISteamFriends {
+  unsafe { sys::SteamAPI_SteamFriends_v017() }
+}
+
+pub fn utils() -> *mut sys::ISteamUtils {
+  unsafe { sys::SteamAPI_SteamUtils_v010() }
+}
+
+/// A call result struct that `register_call_result` can read back
+///
+/// # Safety
+/// `CALLBACK_ID` must be the `k_iCallback` of the implementing struct.
+pub unsafe trait CallResult: Copy + Send + 'static {
+  const CALLBACK_ID: i32;
+}
+
+macro_rules! call_result {
+  ($($ty:ident = $id:expr),* $(,)?) => {
+    $(unsafe impl CallResult for sys::$ty {
+      const CALLBACK_ID: i32 = $id;
+    })*
+  };
+}
+
+call_result! {
+  SetPersonaNameResponse_t = 347,
+  SteamUGCQueryCompleted_t = 3401,
+  SubmitItemUpdateResult_t = 3404,
+  UserFavoriteItemsListChanged_t = 3407,
+  SetUserItemVoteResult_t = 3408,
+  GetUserItemVoteResult_t = 3409,
+  AddUGCDependencyResult_t = 3412,
+  RemoveUGCDependencyResult_t = 3413,
+  AddAppDependencyResult_t = 3414,
+  RemoveAppDependencyResult_t = 3415,
+  GetAppDependenciesResult_t = 3416,
+}
+
+// SteamAPICallCompleted_t::k_iCallback
+const API_CALL_COMPLETED: i32 = 703;
+
+struct RawCallResult {
+  callback_id: i32,
+  data: Vec<u8>,
+  failure: Option<sys::ESteamAPICallFailure>,
+}
+
+type CallResultHandler = Box<dyn FnOnce(RawCallResult) + Send>;
+
+#[derive(Default)]
+struct CallResults {
+  handlers: HashMap<sys::SteamAPICall_t, CallResultHandler>,
+  // Results that came in before their handler was registered
+  unclaimed: HashMap<sys::SteamAPICall_t, RawCallResult>,
+}
+
+// steamworks-rs dispatches the global pipe itself, and `run_callbacks` reads and frees every
+// completed API call on it, including ones it didn't make. Raw calls whose result we need are
+// therefore made on a pipe of our own, which only `run_call_results` drains.
+struct CallPipe {
+  pipe: sys::HSteamPipe,
+  ugc: *mut sys::ISteamUGC,
+  friends: *mut sys::ISteamFriends,
+  utils: *mut sys::ISteamUtils,
+  results: Mutex<CallResults>,
+}
+
+// The interface pointers are handles into steamclient, which can be used from any thread
+unsafe impl Send for CallPipe {}
+unsafe impl Sync for CallPipe {}
+
+static CALL_PIPE: OnceLock<CallPipe> = OnceLock::new();
+
+/// Connects the pipe call results are read from. Call once Steam is initialized.
+pub fn init_call_results() -> Result<(), String> {
+  if CALL_PIPE.get().is_some() {
+    return Ok(());
+  }
+
+  unsafe {
+    let version = sys::STEAMCLIENT_INTERFACE_VERSION.as_ptr() as *const c_char;
+    let client = sys::SteamInternal_CreateInterface(version) as *mut sys::ISteamClient;
+    if client.is_null() {
+      return Err("Steamworks: Failed to get the Steam client interface".to_string());
+    }
+    let pipe = sys::SteamAPI_ISteamClient_CreateSteamPipe(client);
+    if pipe == 0 {
+      return Err("Steamworks: Failed to create a Steam pipe".to_string());
+    }
+    let user = sys::SteamAPI_ISteamClient_ConnectToGlobalUser(client, pipe);
+    if user == 0 {
+      sys::SteamAPI_ISteamClient_BReleaseSteamPipe(client, pipe);
+      return Err("Steamworks: Failed to connect to the Steam user".to_string());
+    }
+
+    let _ = CALL_PIPE.set(CallPipe {
+      pipe,
+      ugc: sys::SteamAPI_ISteamClient_GetISteamUGC(
+        client,
+        user,
+        pipe,
+        sys::STEAMUGC_INTERFACE_VERSION.as_ptr() as *const c_char,
+      ),
+      friends: sys::SteamAPI_ISteamClient_GetISteamFriends(
+        client,
+        user,
+        pipe,
+        sys::STEAMFRIENDS_INTERFACE_VERSION.as_ptr() as *const c_char,
+      ),
+      utils: sys::SteamAPI_ISteamClient_GetISteamUtils(
+        client,
+        pipe,
+        sys::STEAMUTILS_INTERFACE_VERSION.as_ptr() as *const c_char,
+      ),
+      results: Mutex::new(CallResults::default()),
+    });
+  }
+  Ok(())
+}
+
+fn call_pipe() -> &'static CallPipe {
+  CALL_PIPE
+    .get()
+    .expect("Steamworks: init_call_results must run before making API calls")
+}
+
+/// `ISteamUGC` on the call result pipe. Use it for calls passed to `register_call_result`,
+/// and for anything else that has to share a handle with them.
+pub fn call_ugc() -> *mut sys::ISteamUGC {
+  call_pipe().ugc
+}
+
+/// `ISteamFriends` on the call result pipe, see `call_ugc`
+pub fn call_friends() -> *mut sys::ISteamFriends {
+  call_pipe().friends
+}
+
+/// Calls `callback` with the result of `call` once `run_call_results` has read it.
+///
+/// `call` must have been made through `call_ugc` or `call_friends`.
+pub fn register_call_result<T: CallResult>(
+  call: sys::SteamAPICall_t,
+  callback: impl FnOnce(Result<T, String>) + Send + 'static,
+) {
+  if call == 0 {
+    callback(Err("Steamworks: Invalid API call".to_string()));
+    return;
+  }
+
+  let mut results = call_pipe().results.lock().unwrap();
+  match results.unclaimed.remove(&call) {
+    Some(raw) => {
+      drop(results);
+      callback(read_call_result(raw));
+    }
+    None => {
+      results
+        .handlers
+        .insert(call, Box::new(move |raw| callback(read_call_result(raw))));
+    }
+  }
+}
+
+fn read_call_result<T: CallResult>(raw: RawCallResult) -> Result<T, String> {
+  if let Some(reason) = raw.failure {
+    return Err(format!("Steamworks: API call failed: {:?}", reason));
+  }
+  if raw.callback_id != T::CALLBACK_ID || raw.data.len() != size_of::<T>() {
+    return Err(format!(
+      "Steamworks: Unexpected call result {} of {} bytes",
+      raw.callback_id,
+      raw.data.len()
+    ));
+  }
+  Ok(unsafe { (raw.data.as_ptr() as *const T).read_unaligned() })
+}
+
+/// Reads every completed call on the call result pipe and hands it to its handler.
+/// Run alongside `Client::run_callbacks`.
+pub fn run_call_results() {
+  let call_pipe = match CALL_PIPE.get() {
+    Some(call_pipe) => call_pipe,
+    None => return,
+  };
+
+  unsafe {
+    sys::SteamAPI_ManualDispatch_RunFrame(call_pipe.pipe);
+    let mut message = MaybeUninit::<sys::CallbackMsg_t>::zeroed();
+    while sys::SteamAPI_ManualDispatch_GetNextCallback(call_pipe.pipe, message.as_mut_ptr()) {
+      let msg = message.assume_init_ref();
+      // Regular callbacks reach this pipe as well, steamworks-rs already dispatches those
+      if msg.m_iCallback == API_CALL_COMPLETED {
+        let completed = (msg.m_pubParam as *const sys::SteamAPICallCompleted_t).read_unaligned();
+        let raw = call_pipe.read_raw_result(&completed);
+        call_pipe.complete(completed.m_hAsyncCall, raw);
+      }
+      sys::SteamAPI_ManualDispatch_FreeLastCallback(call_pipe.pipe);
+    }
+  }
+}
+
+impl CallPipe {
+  fn read_raw_result(&self, completed: &sys::SteamAPICallCompleted_t) -> RawCallResult {
+    let mut data = vec![0u8; completed.m_cubParam as usize];
+    let mut failed = false;
+    let ok = unsafe {
+      sys::SteamAPI_ManualDispatch_GetAPICallResult(
+        self.pipe,
+        completed.m_hAsyncCall,
+        data.as_mut_ptr() as *mut c_void,
+        data.len() as i32,
+        completed.m_iCallback,
+        &mut failed,
+      )
+    };
+    let failure = (!ok || failed).then(|| unsafe {
+      sys::SteamAPI_ISteamUtils_GetAPICallFailureReason(self.utils, completed.m_hAsyncCall)
+    });
+
+    RawCallResult {
+      callback_id: completed.m_iCallback,
+      data,
+      failure,
+    }
+  }
+
+  fn complete(&self, call: sys::SteamAPICall_t, raw: RawCallResult) {
+    let handler = {
+      let mut results = self.results.lock().unwrap();
+      match results.handlers.remove(&call) {
+        Some(handler) => handler,
+        None => {
+          results.unclaimed.insert(call, raw);
+          return;
+        }
+      }
+    };
+    handler(raw);
+  }
+}
+
+/// Turns the `m_eResult` of a call result into a `SteamError` when it isn't `k_EResultOK`
+pub fn check_result(result: sys::EResult) -> Result<(), steamworks::SteamError> {
+  match result {
+    sys::EResult::k_EResultOK => Ok(()),
+    result => Err(result.into()),
+  }
+}
+
+/// Reads a NUL terminated string out of a buffer Steam filled in
+pub fn string_from_buffer(buffer: &[c_char]) -> String {
+  unsafe { CStr::from_ptr(buffer.as_ptr()) }
+    .to_string_lossy()
+    .into_owned()
+}
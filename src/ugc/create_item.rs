@@ -0,0 +1,58 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, Error, Result};
+use std::sync::mpsc;
+use std::sync::Arc;
+use steamworks::{AppId, FileType};
+
+use super::types::WorkshopItemCreationResult;
+use crate::errors::steam_error_to_napi;
+
+/// Async Task for creating a new, empty workshop item
+pub struct WorkshopCreateItemTask {
+  pub client: Arc<steamworks::Client>,
+  pub app_id: AppId,
+  pub file_type: FileType,
+}
+
+impl WorkshopCreateItemTask {
+  fn create_item(&self) -> Result<WorkshopItemCreationResult> {
+    let (tx, rx) = mpsc::channel();
+
+    self
+      .client
+      .ugc()
+      .create_item(self.app_id, self.file_type, move |result| {
+        let _ = tx.send(result);
+      });
+
+    match rx.recv() {
+      Ok(Ok((item_id, needs_to_accept_agreement))) => Ok(WorkshopItemCreationResult {
+        item_id: item_id.0 as f64,
+        needs_to_accept_agreement,
+      }),
+      Ok(Err(e)) => Err(steam_error_to_napi(e)),
+      Err(e) => Err(Error::from_reason(format!(
+        r"Steamworks: Failed to receive item creation result: {:?}",
+        e
+      ))),
+    }
+  }
+}
+
+#[napi]
+impl Task for WorkshopCreateItemTask {
+  type Output = WorkshopItemCreationResult;
+  type JsValue = WorkshopItemCreationResult;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.create_item()
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
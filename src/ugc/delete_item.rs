@@ -0,0 +1,48 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, Error, Result};
+use std::sync::mpsc;
+use std::sync::Arc;
+
+use crate::errors::steam_error_to_napi;
+
+/// Async Task for deleting a workshop item the current user published
+pub struct WorkshopDeleteItemTask {
+  pub client: Arc<steamworks::Client>,
+  pub item_id: f64,
+}
+
+impl WorkshopDeleteItemTask {
+  fn delete_item(&self) -> Result<()> {
+    let ugc = self.client.ugc();
+    let (tx, rx) = mpsc::channel();
+    ugc.delete_item(steamworks::PublishedFileId(self.item_id as u64), move |result| {
+      let _ = tx.send(result);
+    });
+    match rx.recv() {
+      Ok(Ok(())) => Ok(()),
+      Ok(Err(e)) => Err(steam_error_to_napi(e)),
+      Err(e) => Err(Error::from_reason(format!(
+        r"Steamworks: Failed to receive delete result: {:?}",
+        e
+      ))),
+    }
+  }
+}
+
+#[napi]
+impl Task for WorkshopDeleteItemTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.delete_item()
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
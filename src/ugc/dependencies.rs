@@ -0,0 +1,154 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, Error, Result};
+use std::sync::mpsc;
+use steamworks_sys as sys;
+
+use crate::errors::steam_error_to_napi;
+use crate::steam_api::{self, CallResult};
+
+// Blocks until the pump hands over the result of `call`
+fn receive_call_result<T: CallResult>(call: sys::SteamAPICall_t, action: &str) -> Result<T> {
+  let (tx, rx) = mpsc::channel();
+  steam_api::register_call_result::<T>(call, move |result| {
+    let _ = tx.send(result);
+  });
+  match rx.recv() {
+    Ok(Ok(result)) => Ok(result),
+    Ok(Err(e)) => Err(Error::from_reason(format!(
+      r"Steamworks: Failed to receive {} result: {}",
+      action, e
+    ))),
+    Err(e) => Err(Error::from_reason(format!(
+      r"Steamworks: Failed to receive {} result: {:?}",
+      action, e
+    ))),
+  }
+}
+
+/// Async Task for adding a child item to, or removing it from, a collection or parent item
+pub struct WorkshopItemChildTask {
+  pub parent_id: f64,
+  pub child_id: f64,
+  pub add: bool,
+}
+
+impl WorkshopItemChildTask {
+  fn update_child(&self) -> Result<()> {
+    let ugc = steam_api::call_ugc();
+    let (parent, child) = (self.parent_id as u64, self.child_id as u64);
+
+    if self.add {
+      let call = unsafe { sys::SteamAPI_ISteamUGC_AddDependency(ugc, parent, child) };
+      let result = receive_call_result::<sys::AddUGCDependencyResult_t>(call, "add dependency")?;
+      steam_api::check_result(result.m_eResult).map_err(steam_error_to_napi)
+    } else {
+      let call = unsafe { sys::SteamAPI_ISteamUGC_RemoveDependency(ugc, parent, child) };
+      let result = receive_call_result::<sys::RemoveUGCDependencyResult_t>(call, "remove dependency")?;
+      steam_api::check_result(result.m_eResult).map_err(steam_error_to_napi)
+    }
+  }
+}
+
+#[napi]
+impl Task for WorkshopItemChildTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.update_child()
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
+
+/// Async Task for adding or removing an app (e.g. a DLC) that a workshop item requires
+pub struct WorkshopAppDependencyTask {
+  pub item_id: f64,
+  pub app_id: u32,
+  pub add: bool,
+}
+
+impl WorkshopAppDependencyTask {
+  fn update_app_dependency(&self) -> Result<()> {
+    let ugc = steam_api::call_ugc();
+    let item_id = self.item_id as u64;
+
+    if self.add {
+      let call = unsafe { sys::SteamAPI_ISteamUGC_AddAppDependency(ugc, item_id, self.app_id) };
+      let result = receive_call_result::<sys::AddAppDependencyResult_t>(call, "add app dependency")?;
+      steam_api::check_result(result.m_eResult).map_err(steam_error_to_napi)
+    } else {
+      let call = unsafe { sys::SteamAPI_ISteamUGC_RemoveAppDependency(ugc, item_id, self.app_id) };
+      let result = receive_call_result::<sys::RemoveAppDependencyResult_t>(call, "remove app dependency")?;
+      steam_api::check_result(result.m_eResult).map_err(steam_error_to_napi)
+    }
+  }
+}
+
+#[napi]
+impl Task for WorkshopAppDependencyTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.update_app_dependency()
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
+
+/// Async Task for listing the apps a workshop item requires
+pub struct WorkshopGetAppDependenciesTask {
+  pub item_id: f64,
+}
+
+impl WorkshopGetAppDependenciesTask {
+  fn get_app_dependencies(&self) -> Result<Vec<u32>> {
+    let call = unsafe { sys::SteamAPI_ISteamUGC_GetAppDependencies(steam_api::call_ugc(), self.item_id as u64) };
+    let result = receive_call_result::<sys::GetAppDependenciesResult_t>(call, "app dependencies")?;
+    steam_api::check_result(result.m_eResult).map_err(steam_error_to_napi)?;
+
+    // Steam hands back at most 32 app ids per result, and the call result only carries the first batch,
+    // so fail rather than pass off a partial list as the full one
+    let app_ids = result.m_rgAppIDs;
+    let count = (result.m_nNumAppDependencies as usize).min(app_ids.len());
+    let total = result.m_nTotalNumAppDependencies as usize;
+    if total > count {
+      return Err(Error::from_reason(format!(
+        "Steamworks: Item {} has {} app dependencies but only {} could be read",
+        self.item_id as u64, total, count
+      )));
+    }
+    Ok(app_ids[..count].to_vec())
+  }
+}
+
+#[napi]
+impl Task for WorkshopGetAppDependenciesTask {
+  type Output = Vec<u32>;
+  type JsValue = Vec<u32>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.get_app_dependencies()
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
@@ -0,0 +1,137 @@
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Error, Result};
+use std::sync::Arc;
+use steamworks::{Client, ItemState, PublishedFileId};
+use tokio::sync::oneshot;
+use tokio::time::{sleep, timeout_at, Duration, Instant};
+
+use super::events::WorkshopEvents;
+use super::types::*;
+use crate::errors::steam_error_to_napi;
+use crate::steam_api;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Subscribes to (if needed) and downloads an item, resolving once it is installed and up to date
+pub async fn ensure_installed(
+  client: Arc<Client>,
+  events: Arc<WorkshopEvents>,
+  item_id: f64,
+  options: Option<EnsureInstalledOptions>,
+) -> Result<WorkshopItemInstallInfo> {
+  let file_id = PublishedFileId(item_id as u64);
+  let (high_priority, timeout_ms, on_progress) = match options {
+    Some(options) => (
+      options.high_priority.unwrap_or(true),
+      options.timeout_ms,
+      options.on_progress,
+    ),
+    None => (true, None, None),
+  };
+
+  // The timeout covers subscribing as well, which never answers while Steam is offline
+  let install = install(&client, &events, file_id, high_priority, on_progress.as_ref());
+  match timeout_ms {
+    Some(ms) => match timeout_at(Instant::now() + Duration::from_millis(ms as u64), install).await {
+      Ok(result) => result,
+      Err(_) => Err(Error::from_reason(format!(
+        "Steamworks: Timed out waiting for item {} to install",
+        file_id.0
+      ))),
+    },
+    None => install.await,
+  }
+}
+
+async fn install(
+  client: &Client,
+  events: &WorkshopEvents,
+  file_id: PublishedFileId,
+  high_priority: bool,
+  on_progress: Option<&ThreadsafeFunction<DownloadInfo, ErrorStrategy::Fatal>>,
+) -> Result<WorkshopItemInstallInfo> {
+  if !client.ugc().item_state(file_id).contains(ItemState::SUBSCRIBED) {
+    let (tx, rx) = oneshot::channel();
+    client.ugc().subscribe_item(file_id, move |result| {
+      let _ = tx.send(result);
+    });
+    match rx.await {
+      Ok(Ok(())) => {}
+      Ok(Err(e)) => return Err(steam_error_to_napi(e)),
+      Err(e) => {
+        return Err(Error::from_reason(format!(
+          r"Steamworks: Failed to receive subscription result: {:?}",
+          e
+        )))
+      }
+    }
+  }
+
+  if let Some(info) = installed_info(client, file_id) {
+    return Ok(info);
+  }
+
+  // Listening before starting the download so a quick failure can't slip past
+  let (_waiter, mut download_results) = events.download_results(client, file_id.0);
+  if !client.ugc().download_item(file_id, high_priority) {
+    return Err(Error::from_reason(format!(
+      "Steamworks: Failed to start download of item {}",
+      file_id.0
+    )));
+  }
+
+  // A poll can land between Steam clearing the download flags and setting INSTALLED,
+  // so the download only counts as stopped when two polls in a row see it that way
+  let mut stopped = false;
+  loop {
+    tokio::select! {
+      Some(result) = download_results.recv() => {
+        steam_api::check_result(result).map_err(steam_error_to_napi)?;
+      }
+      _ = sleep(POLL_INTERVAL) => {}
+    }
+
+    if let Some(info) = installed_info(client, file_id) {
+      return Ok(info);
+    }
+
+    let state = client.ugc().item_state(file_id);
+    if state.intersects(ItemState::DOWNLOADING | ItemState::DOWNLOAD_PENDING) {
+      stopped = false;
+    } else if stopped {
+      return Err(Error::from_reason(format!(
+        "Steamworks: Download of item {} stopped before it was installed",
+        file_id.0
+      )));
+    } else {
+      stopped = true;
+    }
+
+    if let Some(callback) = on_progress {
+      if let Some((current, total)) = client.ugc().item_download_info(file_id) {
+        callback.call(
+          DownloadInfo {
+            current: current as f64,
+            total: total as f64,
+          },
+          ThreadsafeFunctionCallMode::NonBlocking,
+        );
+      }
+    }
+  }
+}
+
+// Install info for the item, but only once it is installed and doesn't need an update
+fn installed_info(client: &Client, file_id: PublishedFileId) -> Option<WorkshopItemInstallInfo> {
+  let ugc = client.ugc();
+  let state = ugc.item_state(file_id);
+  if !state.contains(ItemState::INSTALLED) || state.contains(ItemState::NEEDS_UPDATE) {
+    return None;
+  }
+
+  ugc.item_install_info(file_id).map(|install_info| WorkshopItemInstallInfo {
+    folder: install_info.folder,
+    size_on_disk: install_info.size_on_disk as f64,
+    timestamp: install_info.timestamp,
+  })
+}
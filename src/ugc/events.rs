@@ -0,0 +1,117 @@
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction};
+use std::ffi::c_void;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU32, Ordering};
+use steamworks::{Callback, Client};
+use steamworks_sys as sys;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use super::types::WorkshopItemEvent;
+use crate::listeners::{ListenerSet, Waiter};
+
+// ItemInstalled_t, posted when a subscribed item finishes installing or updating
+pub struct ItemInstalled {
+  pub app_id: u32,
+  pub item_id: u64,
+}
+
+unsafe impl Callback for ItemInstalled {
+  const ID: i32 = 3405;
+  const SIZE: i32 = size_of::<sys::ItemInstalled_t>() as i32;
+
+  unsafe fn from_raw(raw: *mut c_void) -> Self {
+    let val = &mut *(raw as *mut sys::ItemInstalled_t);
+    ItemInstalled {
+      app_id: val.m_unAppID,
+      item_id: val.m_nPublishedFileId,
+    }
+  }
+}
+
+// DownloadItemResult_t, posted when a download started with DownloadItem finishes.
+// steamworks-rs has its own version of this, but it doesn't keep the raw result code.
+pub struct DownloadItemResult {
+  pub app_id: u32,
+  pub item_id: u64,
+  pub result: sys::EResult,
+}
+
+unsafe impl Callback for DownloadItemResult {
+  const ID: i32 = 3406;
+  const SIZE: i32 = size_of::<sys::DownloadItemResult_t>() as i32;
+
+  unsafe fn from_raw(raw: *mut c_void) -> Self {
+    let val = &mut *(raw as *mut sys::DownloadItemResult_t);
+    DownloadItemResult {
+      app_id: val.m_unAppID,
+      item_id: val.m_nPublishedFileId,
+      result: val.m_eResult,
+    }
+  }
+}
+
+pub type WorkshopEventCallback = ThreadsafeFunction<WorkshopItemEvent, ErrorStrategy::Fatal>;
+
+/// Listeners for workshop callbacks, shared by every UGCClient of a SteamClient
+#[derive(Default)]
+pub struct WorkshopEvents {
+  next_id: AtomicU32,
+  item_installed: ListenerSet<ItemInstalled>,
+  download_item_result: ListenerSet<DownloadItemResult>,
+}
+
+impl WorkshopEvents {
+  pub fn on_item_installed(&self, client: &Client, callback: WorkshopEventCallback) -> u32 {
+    let listener_id = self.next_id();
+    self
+      .item_installed
+      .add(client, listener_id, callback, |event: &ItemInstalled| WorkshopItemEvent {
+        app_id: event.app_id,
+        item_id: event.item_id as f64,
+        result: sys::EResult::k_EResultOK as i32, // Steam only posts this once the install worked
+      });
+    listener_id
+  }
+
+  pub fn on_download_item_result(&self, client: &Client, callback: WorkshopEventCallback) -> u32 {
+    let listener_id = self.next_id();
+    self
+      .download_item_result
+      .add(client, listener_id, callback, |event: &DownloadItemResult| WorkshopItemEvent {
+        app_id: event.app_id,
+        item_id: event.item_id as f64,
+        result: event.result as i32,
+      });
+    listener_id
+  }
+
+  /// Receives the result of every download of `item_id` that finishes while the waiter is alive
+  pub fn download_results(
+    &self,
+    client: &Client,
+    item_id: u64,
+  ) -> (Waiter<'_, DownloadItemResult>, UnboundedReceiver<sys::EResult>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let waiter = self
+      .download_item_result
+      .wait(client, self.next_id(), move |event: &DownloadItemResult| {
+        if event.item_id == item_id {
+          let _ = tx.send(event.result);
+        }
+      });
+    (waiter, rx)
+  }
+
+  pub fn remove(&self, listener_id: u32) -> bool {
+    self.item_installed.remove(listener_id) || self.download_item_result.remove(listener_id)
+  }
+
+  pub fn remove_all(&self) {
+    self.item_installed.clear();
+    self.download_item_result.clear();
+  }
+
+  fn next_id(&self) -> u32 {
+    self.next_id.fetch_add(1, Ordering::Relaxed)
+  }
+}
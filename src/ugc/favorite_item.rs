@@ -0,0 +1,61 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, Error, Result};
+use std::sync::mpsc;
+use std::sync::Arc;
+use steamworks_sys as sys;
+
+use crate::errors::steam_error_to_napi;
+use crate::steam_api;
+
+/// Async Task for adding a workshop item to, or removing it from, the user's favorites
+pub struct WorkshopFavoriteItemTask {
+  pub client: Arc<steamworks::Client>,
+  pub item_id: f64,
+  pub favorite: bool,
+}
+
+impl WorkshopFavoriteItemTask {
+  fn favorite_item(&self) -> Result<()> {
+    let ugc = steam_api::call_ugc();
+    let app_id = self.client.utils().app_id().0;
+    let item_id = self.item_id as u64;
+
+    let (tx, rx) = mpsc::channel();
+    let call = unsafe {
+      if self.favorite {
+        sys::SteamAPI_ISteamUGC_AddItemToFavorites(ugc, app_id, item_id)
+      } else {
+        sys::SteamAPI_ISteamUGC_RemoveItemFromFavorites(ugc, app_id, item_id)
+      }
+    };
+    steam_api::register_call_result::<sys::UserFavoriteItemsListChanged_t>(call, move |result| {
+      let _ = tx.send(result);
+    });
+    match rx.recv() {
+      Ok(Ok(result)) => steam_api::check_result(result.m_eResult).map_err(steam_error_to_napi),
+      Ok(Err(e)) => Err(Error::from_reason(e)),
+      Err(e) => Err(Error::from_reason(format!(
+        r"Steamworks: Failed to receive favorites result: {:?}",
+        e
+      ))),
+    }
+  }
+}
+
+#[napi]
+impl Task for WorkshopFavoriteItemTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.favorite_item()
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
@@ -0,0 +1,203 @@
+use napi::{Error, Result};
+use std::collections::HashMap;
+use std::ffi::c_char;
+use std::mem::MaybeUninit;
+use std::sync::mpsc;
+use steamworks_sys as sys;
+
+use super::types::*;
+use crate::errors::steam_error_to_napi;
+use crate::steam_api;
+
+// Buffer sizes from isteamugc.h / isteamremotestorage.h
+pub const METADATA_MAX: usize = 5000; // k_cchDeveloperMetadataMax
+const URL_MAX: usize = 1024; // k_cchPublishedFileURLMax
+const FILE_NAME_MAX: usize = 260; // k_cchFilenameMax
+const KEY_VALUE_MAX: usize = 256;
+
+// Per-item data steamworks-rs can't read back from a query
+#[derive(Default)]
+pub struct ItemExtras {
+  pub metadata: Option<String>,
+  pub key_value_tags: Option<Vec<WorkshopKeyValueTag>>,
+  pub additional_previews: Option<Vec<WorkshopItemPreview>>,
+}
+
+/// Runs a raw details request for `item_ids` and reads back whatever `options` asks for
+pub fn fetch_item_extras(
+  item_ids: &[u64],
+  options: &WorkshopQueryOptions,
+) -> Result<HashMap<u64, ItemExtras>> {
+  let include_metadata = options.include_metadata.unwrap_or(false);
+  let include_key_value_tags = options.include_key_value_tags.unwrap_or(false);
+  let include_additional_previews = options.include_additional_previews.unwrap_or(false);
+
+  let configure = |ugc, handle| unsafe {
+    sys::SteamAPI_ISteamUGC_SetReturnMetadata(ugc, handle, include_metadata);
+    sys::SteamAPI_ISteamUGC_SetReturnKeyValueTags(ugc, handle, include_key_value_tags);
+    sys::SteamAPI_ISteamUGC_SetReturnAdditionalPreviews(ugc, handle, include_additional_previews);
+    // The items were just fetched, so let Steam answer from its cache like the original query could
+    if let Some(max_age) = options.cache_max_age_seconds {
+      sys::SteamAPI_ISteamUGC_SetAllowCachedResponse(ugc, handle, max_age);
+    }
+  };
+  run_details_request(item_ids, configure, |ugc, handle, index, _| ItemExtras {
+    metadata: include_metadata
+      .then(|| read_metadata(ugc, handle, index))
+      .flatten(),
+    key_value_tags: include_key_value_tags.then(|| read_key_value_tags(ugc, handle, index)),
+    additional_previews: include_additional_previews
+      .then(|| read_additional_previews(ugc, handle, index)),
+  })
+}
+
+/// The result Steam gave for each of `item_ids`, e.g. access denied for a private item.
+/// steamworks-rs skips every item whose result isn't OK, so this is the only way to see why.
+pub fn fetch_item_results(item_ids: &[u64]) -> Result<HashMap<u64, sys::EResult>> {
+  run_details_request(item_ids, |_, _| {}, |_, _, _, details| details.m_eResult)
+}
+
+// Runs a raw details request for `item_ids`, set up by `configure`, and calls `read` for every
+// result Steam returned
+fn run_details_request<T>(
+  item_ids: &[u64],
+  configure: impl FnOnce(*mut sys::ISteamUGC, sys::UGCQueryHandle_t),
+  mut read: impl FnMut(*mut sys::ISteamUGC, sys::UGCQueryHandle_t, u32, &sys::SteamUGCDetails_t) -> T,
+) -> Result<HashMap<u64, T>> {
+  let ugc = steam_api::call_ugc();
+  let mut file_ids = item_ids.to_vec();
+  let handle = unsafe {
+    sys::SteamAPI_ISteamUGC_CreateQueryUGCDetailsRequest(
+      ugc,
+      file_ids.as_mut_ptr(),
+      file_ids.len() as u32,
+    )
+  };
+  if handle == u64::MAX {
+    return Err(Error::from_reason(
+      "Steamworks: Failed to create workshop details request",
+    ));
+  }
+  configure(ugc, handle);
+
+  let (tx, rx) = mpsc::channel();
+  let call = unsafe { sys::SteamAPI_ISteamUGC_SendQueryUGCRequest(ugc, handle) };
+  steam_api::register_call_result::<sys::SteamUGCQueryCompleted_t>(call, move |result| {
+    let _ = tx.send(result);
+  });
+  let completed = match rx.recv() {
+    Ok(Ok(completed)) => steam_api::check_result(completed.m_eResult)
+      .map(|_| completed)
+      .map_err(steam_error_to_napi),
+    Ok(Err(e)) => Err(Error::from_reason(e)),
+    Err(e) => Err(Error::from_reason(format!(
+      r"Steamworks: Failed to receive workshop item details: {:?}",
+      e
+    ))),
+  };
+
+  let results = completed.map(|completed| {
+    (0..completed.m_unNumResultsReturned)
+      .filter_map(|index| {
+        let mut details = MaybeUninit::<sys::SteamUGCDetails_t>::zeroed();
+        let ok = unsafe {
+          sys::SteamAPI_ISteamUGC_GetQueryUGCResult(ugc, handle, index, details.as_mut_ptr())
+        };
+        if !ok {
+          return None;
+        }
+        let details = unsafe { details.assume_init() };
+        Some((details.m_nPublishedFileId, read(ugc, handle, index, &details)))
+      })
+      .collect::<HashMap<_, _>>()
+  });
+
+  unsafe { sys::SteamAPI_ISteamUGC_ReleaseQueryUGCRequest(ugc, handle) };
+
+  results
+}
+
+fn read_metadata(ugc: *mut sys::ISteamUGC, handle: sys::UGCQueryHandle_t, index: u32) -> Option<String> {
+  let mut buffer = vec![0 as c_char; METADATA_MAX + 1];
+  let ok = unsafe {
+    sys::SteamAPI_ISteamUGC_GetQueryUGCMetadata(
+      ugc,
+      handle,
+      index,
+      buffer.as_mut_ptr(),
+      buffer.len() as u32,
+    )
+  };
+  ok.then(|| steam_api::string_from_buffer(&buffer))
+}
+
+fn read_key_value_tags(
+  ugc: *mut sys::ISteamUGC,
+  handle: sys::UGCQueryHandle_t,
+  index: u32,
+) -> Vec<WorkshopKeyValueTag> {
+  let count = unsafe { sys::SteamAPI_ISteamUGC_GetQueryUGCNumKeyValueTags(ugc, handle, index) };
+
+  (0..count)
+    .filter_map(|tag_index| {
+      let mut key = [0 as c_char; KEY_VALUE_MAX];
+      let mut value = [0 as c_char; KEY_VALUE_MAX];
+      let ok = unsafe {
+        sys::SteamAPI_ISteamUGC_GetQueryUGCKeyValueTag(
+          ugc,
+          handle,
+          index,
+          tag_index,
+          key.as_mut_ptr(),
+          key.len() as u32,
+          value.as_mut_ptr(),
+          value.len() as u32,
+        )
+      };
+      ok.then(|| WorkshopKeyValueTag {
+        key: steam_api::string_from_buffer(&key),
+        value: steam_api::string_from_buffer(&value),
+      })
+    })
+    .collect()
+}
+
+fn read_additional_previews(
+  ugc: *mut sys::ISteamUGC,
+  handle: sys::UGCQueryHandle_t,
+  index: u32,
+) -> Vec<WorkshopItemPreview> {
+  let count = unsafe { sys::SteamAPI_ISteamUGC_GetQueryUGCNumAdditionalPreviews(ugc, handle, index) };
+
+  (0..count)
+    .filter_map(|preview_index| {
+      let mut url = vec![0 as c_char; URL_MAX];
+      let mut file_name = vec![0 as c_char; FILE_NAME_MAX];
+      let mut preview_type = sys::EItemPreviewType::k_EItemPreviewType_Image;
+      let ok = unsafe {
+        sys::SteamAPI_ISteamUGC_GetQueryUGCAdditionalPreview(
+          ugc,
+          handle,
+          index,
+          preview_index,
+          url.as_mut_ptr(),
+          url.len() as u32,
+          file_name.as_mut_ptr(),
+          file_name.len() as u32,
+          &mut preview_type,
+        )
+      };
+      if !ok {
+        return None;
+      }
+
+      let original_file_name = steam_api::string_from_buffer(&file_name);
+      Some(WorkshopItemPreview {
+        index: preview_index,
+        preview_type: WorkshopItemPreviewType::from_raw(preview_type as u32)?,
+        url_or_video_id: steam_api::string_from_buffer(&url),
+        original_file_name: (!original_file_name.is_empty()).then_some(original_file_name),
+      })
+    })
+    .collect()
+}
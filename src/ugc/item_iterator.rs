@@ -0,0 +1,96 @@
+use napi::bindgen_prelude::*;
+use napi::{CallContext, Env, JsObject, JsSymbol, Result};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use super::query::{apply_query_options, fetch_page, PagedQuery};
+use super::types::*;
+
+struct IteratorState {
+  query: Box<dyn PagedQuery>,
+  buffer: VecDeque<WorkshopItemDetails>,
+  next_page: Option<u32>,
+}
+
+// Lazily walks every page of a workshop query, one item at a time
+#[napi]
+pub struct WorkshopItemIterator {
+  state: Arc<Mutex<IteratorState>>,
+}
+
+impl WorkshopItemIterator {
+  /// Wraps `query` in an object JS can use with `for await`
+  pub fn create(env: Env, query: Box<dyn PagedQuery>, first_page: u32) -> Result<JsObject> {
+    let iterator = WorkshopItemIterator {
+      state: Arc::new(Mutex::new(IteratorState {
+        query,
+        buffer: VecDeque::new(),
+        next_page: Some(first_page.max(1)),
+      })),
+    };
+
+    let mut object = iterator.into_instance(env)?.as_object(env);
+    let async_iterator = env
+      .get_global()?
+      .get_named_property::<JsObject>("Symbol")?
+      .get_named_property::<JsSymbol>("asyncIterator")?;
+    object.set_property(async_iterator, env.create_function("asyncIterator", return_this)?)?;
+
+    Ok(object)
+  }
+}
+
+#[js_function(0)]
+fn return_this(ctx: CallContext) -> Result<JsObject> {
+  ctx.this()
+}
+
+#[napi]
+impl WorkshopItemIterator {
+  #[napi(ts_return_type = "Promise<IteratorResult<WorkshopItemDetails, undefined>>")]
+  pub fn next(&self) -> AsyncTask<WorkshopItemIteratorNextTask> {
+    AsyncTask::new(WorkshopItemIteratorNextTask {
+      state: self.state.clone(),
+    })
+  }
+}
+
+/// Async Task for pulling the next item, fetching another page when the current one runs out
+pub struct WorkshopItemIteratorNextTask {
+  state: Arc<Mutex<IteratorState>>,
+}
+
+#[napi]
+impl Task for WorkshopItemIteratorNextTask {
+  type Output = WorkshopItemIteratorResult;
+  type JsValue = WorkshopItemIteratorResult;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let mut state = self.state.lock().unwrap();
+
+    while state.buffer.is_empty() {
+      let page = match state.next_page {
+        Some(page) => page,
+        None => break,
+      };
+      let query_handle = apply_query_options(state.query.create_query_handle(page)?, state.query.options());
+      let result = fetch_page(query_handle, state.query.options(), page)?;
+      state.buffer.extend(result.items);
+      state.next_page = result.next_cursor;
+    }
+
+    let value = state.buffer.pop_front();
+    Ok(WorkshopItemIteratorResult {
+      done: value.is_none(),
+      value,
+    })
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
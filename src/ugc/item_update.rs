@@ -0,0 +1,144 @@
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, Error, Result};
+use std::sync::{Arc, Condvar, Mutex};
+use steamworks::AppId;
+use steamworks_sys as sys;
+
+use super::submit_item_update::{begin_item_update, item_update_progress, on_item_update_result, submit_item_update};
+use super::types::*;
+
+type UpdateOutcome = std::result::Result<(f64, bool), String>;
+
+// Shared between the call result handler, the waiters and the progress poller.
+// The handler stores the outcome and wakes every waiter; nothing holds the lock while blocking on Steam.
+struct UpdateState {
+  outcome: Mutex<Option<UpdateOutcome>>,
+  finished: Condvar,
+}
+
+impl UpdateState {
+  fn is_finished(&self) -> bool {
+    self.outcome.lock().unwrap().is_some()
+  }
+}
+
+// A workshop item update that has been submitted and is uploading
+#[napi]
+pub struct WorkshopItemUpdate {
+  handle: sys::UGCUpdateHandle_t,
+  state: Arc<UpdateState>,
+}
+
+impl WorkshopItemUpdate {
+  pub fn submit(app_id: AppId, item_id: f64, details: &WorkshopItemUpdateDetails) -> Result<Self> {
+    let handle = begin_item_update(app_id, item_id, details)?;
+    let call = submit_item_update(handle, &details.changelog)?;
+
+    let state = Arc::new(UpdateState {
+      outcome: Mutex::new(None),
+      finished: Condvar::new(),
+    });
+    let result_state = state.clone();
+    on_item_update_result(call, move |result| {
+      let outcome = result
+        .map(|result| (result.item_id, result.needs_to_accept_agreement))
+        .map_err(|e| e.reason);
+      *result_state.outcome.lock().unwrap() = Some(outcome);
+      result_state.finished.notify_all();
+    });
+
+    Ok(Self { handle, state })
+  }
+
+  fn progress(handle: sys::UGCUpdateHandle_t) -> WorkshopItemUpdateProgress {
+    let (status, processed, total) = item_update_progress(handle);
+    WorkshopItemUpdateProgress {
+      status: WorkshopItemUpdateStatus::from_raw(status),
+      bytes_processed: processed as f64,
+      bytes_total: total as f64,
+    }
+  }
+}
+
+#[napi]
+impl WorkshopItemUpdate {
+  #[napi]
+  pub fn get_progress(&self) -> Result<WorkshopItemUpdateProgress> {
+    Ok(Self::progress(self.handle))
+  }
+
+  // True once Steam has accepted or rejected the update
+  #[napi]
+  pub fn is_finished(&self) -> bool {
+    self.state.is_finished()
+  }
+
+  // Calls `callback` with the current progress every `interval_ms` until the upload finishes
+  #[napi]
+  pub fn on_progress(
+    &self,
+    callback: ThreadsafeFunction<WorkshopItemUpdateProgress, ErrorStrategy::Fatal>,
+    interval_ms: Option<u32>,
+  ) {
+    let handle = self.handle;
+    let state = self.state.clone();
+    let interval_ms = interval_ms.unwrap_or(100).max(1) as u64;
+
+    tokio::spawn(async move {
+      let mut intv = tokio::time::interval(tokio::time::Duration::from_millis(interval_ms));
+      loop {
+        intv.tick().await;
+        let finished = state.is_finished();
+        callback.call(WorkshopItemUpdate::progress(handle), ThreadsafeFunctionCallMode::NonBlocking);
+        if finished {
+          break;
+        }
+      }
+    });
+  }
+
+  // Resolves with the item id once Steam has accepted the update
+  #[napi]
+  pub fn wait(&self) -> AsyncTask<WorkshopItemUpdateWaitTask> {
+    AsyncTask::new(WorkshopItemUpdateWaitTask {
+      state: self.state.clone(),
+    })
+  }
+}
+
+/// Async Task for waiting on a submitted workshop item update
+pub struct WorkshopItemUpdateWaitTask {
+  state: Arc<UpdateState>,
+}
+
+#[napi]
+impl Task for WorkshopItemUpdateWaitTask {
+  type Output = WorkshopItemCreationResult;
+  type JsValue = WorkshopItemCreationResult;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let mut outcome = self.state.outcome.lock().unwrap();
+    while outcome.is_none() {
+      outcome = self.state.finished.wait(outcome).unwrap();
+    }
+    match outcome.clone() {
+      Some(Ok((item_id, needs_to_accept_agreement))) => Ok(WorkshopItemCreationResult {
+        item_id,
+        needs_to_accept_agreement,
+      }),
+      Some(Err(reason)) => Err(Error::from_reason(reason)),
+      None => Err(Error::from_reason(
+        "Steamworks: Failed to receive item update result",
+      )),
+    }
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
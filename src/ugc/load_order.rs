@@ -0,0 +1,280 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, Error, Result};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use steamworks::PublishedFileId;
+
+use super::query::{apply_query_options, fetch_details, RESULTS_PER_PAGE};
+use super::types::*;
+
+/// Dependency edges between workshop items, kept behind a trait so the resolver
+/// doesn't care whether they came from Steam or from an in-memory fixture
+pub trait ItemGraph {
+  /// The items `item_id` requires, or `None` if the item itself is unknown
+  fn dependencies(&self, item_id: u64) -> Option<&[u64]>;
+}
+
+impl ItemGraph for HashMap<u64, Vec<u64>> {
+  fn dependencies(&self, item_id: u64) -> Option<&[u64]> {
+    self.get(&item_id).map(|deps| deps.as_slice())
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingDependency {
+  pub item_id: u64,
+  // Empty when `item_id` was one of the requested items but isn't in the graph
+  pub required_by: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadOrder {
+  // Every dependency comes before the items requiring it, apart from within a cycle
+  pub order: Vec<u64>,
+  pub cycles: Vec<Vec<u64>>,
+  pub missing: Vec<MissingDependency>,
+}
+
+#[derive(Clone, Copy)]
+enum Mark {
+  Visiting,
+  Done,
+}
+
+struct Resolver<'a, G: ItemGraph> {
+  graph: &'a G,
+  wanted: HashSet<u64>,
+  marks: HashMap<u64, Mark>,
+  stack: Vec<u64>,
+  missing: HashMap<u64, usize>,
+  result: LoadOrder,
+}
+
+impl<'a, G: ItemGraph> Resolver<'a, G> {
+  fn report_missing(&mut self, item_id: u64, required_by: Option<u64>) {
+    let index = match self.missing.get(&item_id) {
+      Some(index) => *index,
+      None => {
+        self.result.missing.push(MissingDependency {
+          item_id,
+          required_by: Vec::new(),
+        });
+        self.missing.insert(item_id, self.result.missing.len() - 1);
+        self.result.missing.len() - 1
+      }
+    };
+    if let Some(parent) = required_by {
+      let required_by = &mut self.result.missing[index].required_by;
+      if !required_by.contains(&parent) {
+        required_by.push(parent);
+      }
+    }
+  }
+
+  fn visit(&mut self, item_id: u64, parent: Option<u64>) {
+    match self.marks.get(&item_id) {
+      Some(Mark::Done) => return,
+      Some(Mark::Visiting) => {
+        // Back edge: everything on the stack from `item_id` up forms the cycle
+        let start = self.stack.iter().position(|id| *id == item_id).unwrap_or(0);
+        self.result.cycles.push(self.stack[start..].to_vec());
+        return;
+      }
+      None => {}
+    }
+
+    let graph = self.graph;
+    let dependencies = match graph.dependencies(item_id) {
+      Some(dependencies) => dependencies,
+      None => {
+        self.marks.insert(item_id, Mark::Done);
+        self.report_missing(item_id, parent);
+        return;
+      }
+    };
+
+    self.marks.insert(item_id, Mark::Visiting);
+    self.stack.push(item_id);
+    for dependency in dependencies {
+      if self.wanted.contains(dependency) {
+        self.visit(*dependency, Some(item_id));
+      } else {
+        self.report_missing(*dependency, Some(item_id));
+      }
+    }
+    self.stack.pop();
+    self.marks.insert(item_id, Mark::Done);
+    self.result.order.push(item_id);
+  }
+}
+
+/// Sorts `item_ids` so every item loads after the items it depends on.
+///
+/// Dependencies outside of `item_ids` are reported as missing rather than pulled in.
+/// Items taking part in a cycle are still placed in the order, just not in a
+/// meaningful one relative to each other. Ties keep the order of `item_ids`.
+pub fn resolve_load_order(graph: &impl ItemGraph, item_ids: &[u64]) -> LoadOrder {
+  let mut resolver = Resolver {
+    graph,
+    wanted: item_ids.iter().copied().collect(),
+    marks: HashMap::new(),
+    stack: Vec::new(),
+    missing: HashMap::new(),
+    result: LoadOrder::default(),
+  };
+  for item_id in item_ids {
+    resolver.visit(*item_id, None);
+  }
+  resolver.result
+}
+
+/// Async Task for fetching the dependencies of a set of items and sorting them into a load order
+pub struct WorkshopResolveLoadOrderTask {
+  pub client: Arc<steamworks::Client>,
+  pub item_ids: Vec<f64>,
+}
+
+impl WorkshopResolveLoadOrderTask {
+  // Items Steam doesn't know about are left out, so the resolver reports them as missing
+  fn fetch_graph(&self) -> Result<HashMap<u64, Vec<u64>>> {
+    let options = Some(WorkshopQueryOptions {
+      include_children: Some(true),
+      ..Default::default()
+    });
+
+    let mut graph = HashMap::with_capacity(self.item_ids.len());
+    for batch in self.item_ids.chunks(RESULTS_PER_PAGE as usize) {
+      let ids = batch
+        .iter()
+        .map(|id| PublishedFileId(*id as u64))
+        .collect::<Vec<_>>();
+      let query_handle = match self.client.ugc().query_items(ids) {
+        Ok(handle) => handle,
+        Err(e) => return Err(Error::from_reason(e.to_string())),
+      };
+      let details = fetch_details(apply_query_options(query_handle, &options), &options)?;
+      graph.extend(
+        details
+          .into_iter()
+          .filter(|d| d.time_created != 0)
+          .map(|d| {
+            let children = d.children.unwrap_or_default();
+            (d.item_id as u64, children.into_iter().map(|id| id as u64).collect())
+          }),
+      );
+    }
+    Ok(graph)
+  }
+}
+
+#[napi]
+impl Task for WorkshopResolveLoadOrderTask {
+  type Output = LoadOrder;
+  type JsValue = WorkshopLoadOrder;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let graph = self.fetch_graph()?;
+    let item_ids = self.item_ids.iter().map(|id| *id as u64).collect::<Vec<_>>();
+    Ok(resolve_load_order(&graph, &item_ids))
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(WorkshopLoadOrder {
+      order: output.order.into_iter().map(|id| id as f64).collect(),
+      cycles: output
+        .cycles
+        .into_iter()
+        .map(|cycle| cycle.into_iter().map(|id| id as f64).collect())
+        .collect(),
+      missing: output
+        .missing
+        .into_iter()
+        .map(|m| WorkshopMissingDependency {
+          item_id: m.item_id as f64,
+          required_by: m.required_by.into_iter().map(|id| id as f64).collect(),
+        })
+        .collect(),
+    })
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn fake_graph(edges: &[(u64, &[u64])]) -> HashMap<u64, Vec<u64>> {
+    edges.iter().map(|(id, deps)| (*id, deps.to_vec())).collect()
+  }
+
+  #[test]
+  fn dependencies_load_first() {
+    let graph = fake_graph(&[(1, &[2]), (2, &[3]), (3, &[])]);
+    let load_order = resolve_load_order(&graph, &[1, 2, 3]);
+
+    assert_eq!(load_order.order, vec![3, 2, 1]);
+    assert!(load_order.cycles.is_empty());
+    assert!(load_order.missing.is_empty());
+  }
+
+  #[test]
+  fn ties_keep_input_order() {
+    let graph = fake_graph(&[(5, &[]), (3, &[]), (9, &[])]);
+    assert_eq!(resolve_load_order(&graph, &[5, 3, 9]).order, vec![5, 3, 9]);
+
+    let graph = fake_graph(&[(1, &[3]), (2, &[3]), (3, &[])]);
+    assert_eq!(resolve_load_order(&graph, &[1, 2, 3]).order, vec![3, 1, 2]);
+  }
+
+  #[test]
+  fn reports_two_item_cycle() {
+    let graph = fake_graph(&[(1, &[2]), (2, &[1])]);
+    let load_order = resolve_load_order(&graph, &[1, 2]);
+
+    assert_eq!(load_order.cycles, vec![vec![1, 2]]);
+    assert_eq!(load_order.order, vec![2, 1]);
+    assert!(load_order.missing.is_empty());
+  }
+
+  #[test]
+  fn reports_three_item_cycle() {
+    let graph = fake_graph(&[(1, &[2]), (2, &[3]), (3, &[1])]);
+    let load_order = resolve_load_order(&graph, &[1, 2, 3]);
+
+    assert_eq!(load_order.cycles, vec![vec![1, 2, 3]]);
+    assert_eq!(load_order.order, vec![3, 2, 1]);
+  }
+
+  #[test]
+  fn reports_dependency_outside_requested_items() {
+    let graph = fake_graph(&[(1, &[2, 7]), (2, &[]), (3, &[7]), (7, &[])]);
+    let load_order = resolve_load_order(&graph, &[1, 2, 3]);
+
+    assert_eq!(load_order.order, vec![2, 1, 3]);
+    assert_eq!(
+      load_order.missing,
+      vec![MissingDependency {
+        item_id: 7,
+        required_by: vec![1, 3],
+      }]
+    );
+  }
+
+  #[test]
+  fn reports_requested_item_missing_from_graph() {
+    let graph = fake_graph(&[(1, &[])]);
+    let load_order = resolve_load_order(&graph, &[1, 4]);
+
+    assert_eq!(load_order.order, vec![1]);
+    assert_eq!(
+      load_order.missing,
+      vec![MissingDependency {
+        item_id: 4,
+        required_by: vec![],
+      }]
+    );
+  }
+}
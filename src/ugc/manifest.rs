@@ -0,0 +1,211 @@
+use napi::{Error, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use steamworks::Client;
+
+use super::raw_item_state;
+use super::types::*;
+
+// The parts of a saved manifest needed for diffing. The JSON comes from
+// `JSON.stringify` on a WorkshopSubscriptionManifest, hence the camelCase.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedManifest {
+  items: Vec<SavedManifestItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedManifestItem {
+  item_id: f64,
+  size_on_disk: Option<f64>,
+  timestamp: Option<u32>,
+}
+
+/// Records the state and install info of every subscribed item
+pub fn snapshot(client: &Client) -> WorkshopSubscriptionManifest {
+  let ugc = client.ugc();
+
+  let items = ugc
+    .subscribed_items()
+    .into_iter()
+    .map(|file_id| {
+      let install_info = ugc.item_install_info(file_id);
+      WorkshopManifestItem {
+        item_id: file_id.0 as f64,
+        states: WorkshopItemStateEnum::from_raw_state(raw_item_state(file_id.0)),
+        folder: install_info.as_ref().map(|info| info.folder.clone()),
+        size_on_disk: install_info.as_ref().map(|info| info.size_on_disk as f64),
+        timestamp: install_info.as_ref().map(|info| info.timestamp),
+      }
+    })
+    .collect();
+
+  WorkshopSubscriptionManifest { items }
+}
+
+/// Takes a fresh snapshot and compares it against a previously saved one
+pub fn diff(client: &Client, previous_json: &str) -> Result<WorkshopManifestDiff> {
+  let previous: SavedManifest = serde_json::from_str(previous_json).map_err(|e| {
+    Error::from_reason(format!("Failed to parse subscription manifest: {}", e))
+  })?;
+  let manifest = snapshot(client);
+  Ok(diff_manifests(previous, manifest, |folder| Path::new(folder).exists()))
+}
+
+// Compares a saved manifest against a current snapshot, which ends up as the diff's manifest.
+// `folder_exists` is only asked about the install folders of installed items.
+fn diff_manifests(
+  previous: SavedManifest,
+  manifest: WorkshopSubscriptionManifest,
+  folder_exists: impl Fn(&str) -> bool,
+) -> WorkshopManifestDiff {
+  let mut previous = previous
+    .items
+    .into_iter()
+    .map(|item| (item.item_id as u64, item))
+    .collect::<HashMap<_, _>>();
+
+  let mut added = Vec::new();
+  let mut updated = Vec::new();
+  let mut broken = Vec::new();
+
+  for item in &manifest.items {
+    if is_broken(item, &folder_exists) {
+      broken.push(item.clone());
+    }
+
+    match previous.remove(&(item.item_id as u64)) {
+      None => added.push(item.clone()),
+      Some(saved) if saved.timestamp != item.timestamp || saved.size_on_disk != item.size_on_disk => {
+        updated.push(item.clone())
+      }
+      Some(_) => {}
+    }
+  }
+
+  let mut removed = previous.into_keys().map(|id| id as f64).collect::<Vec<_>>();
+  removed.sort_by(|a, b| a.total_cmp(b));
+
+  WorkshopManifestDiff {
+    added,
+    removed,
+    updated,
+    broken,
+    manifest,
+  }
+}
+
+// Judged from the states the snapshot recorded, so it can't disagree with them
+fn is_broken(item: &WorkshopManifestItem, folder_exists: impl Fn(&str) -> bool) -> bool {
+  let has = |state: WorkshopItemStateEnum| item.states.iter().any(|s| *s as u32 == state as u32);
+
+  if !has(WorkshopItemStateEnum::Installed) {
+    return !has(WorkshopItemStateEnum::Downloading) && !has(WorkshopItemStateEnum::DownloadPending);
+  }
+  match &item.folder {
+    Some(folder) => !folder_exists(folder),
+    None => true,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn item(
+    item_id: f64,
+    states: Vec<WorkshopItemStateEnum>,
+    folder: Option<&str>,
+    timestamp: u32,
+  ) -> WorkshopManifestItem {
+    WorkshopManifestItem {
+      item_id,
+      states,
+      folder: folder.map(|f| f.to_string()),
+      size_on_disk: Some(100.0),
+      timestamp: Some(timestamp),
+    }
+  }
+
+  fn installed(item_id: f64, timestamp: u32) -> WorkshopManifestItem {
+    let states = vec![WorkshopItemStateEnum::Subscribed, WorkshopItemStateEnum::Installed];
+    item(item_id, states, Some("/workshop/item"), timestamp)
+  }
+
+  fn saved(json: &str) -> SavedManifest {
+    serde_json::from_str(json).unwrap()
+  }
+
+  fn ids(items: &[WorkshopManifestItem]) -> Vec<f64> {
+    items.iter().map(|item| item.item_id).collect()
+  }
+
+  #[test]
+  fn reports_added_removed_and_updated_items() {
+    let previous = saved(
+      r#"{"items": [
+        {"itemId": 1, "sizeOnDisk": 100, "timestamp": 10},
+        {"itemId": 2, "sizeOnDisk": 100, "timestamp": 10},
+        {"itemId": 4, "sizeOnDisk": 100, "timestamp": 10}
+      ]}"#,
+    );
+    let current = WorkshopSubscriptionManifest {
+      items: vec![installed(1.0, 10), installed(2.0, 20), installed(3.0, 10)],
+    };
+    let diff = diff_manifests(previous, current, |_| true);
+
+    assert_eq!(ids(&diff.added), vec![3.0]);
+    assert_eq!(ids(&diff.updated), vec![2.0]);
+    assert_eq!(diff.removed, vec![4.0]);
+    assert!(diff.broken.is_empty());
+    assert_eq!(ids(&diff.manifest.items), vec![1.0, 2.0, 3.0]);
+  }
+
+  #[test]
+  fn a_size_change_counts_as_an_update() {
+    let previous = saved(r#"{"items": [{"itemId": 1, "sizeOnDisk": 50, "timestamp": 10}]}"#);
+    let current = WorkshopSubscriptionManifest {
+      items: vec![installed(1.0, 10)],
+    };
+    let diff = diff_manifests(previous, current, |_| true);
+
+    assert_eq!(ids(&diff.updated), vec![1.0]);
+    assert!(diff.added.is_empty());
+  }
+
+  #[test]
+  fn broken_when_not_installed_and_not_downloading() {
+    let previous = saved(r#"{"items": []}"#);
+    let current = WorkshopSubscriptionManifest {
+      items: vec![
+        item(1.0, vec![WorkshopItemStateEnum::Subscribed], None, 0),
+        item(2.0, vec![WorkshopItemStateEnum::Subscribed, WorkshopItemStateEnum::Downloading], None, 0),
+        item(3.0, vec![WorkshopItemStateEnum::Subscribed, WorkshopItemStateEnum::DownloadPending], None, 0),
+        item(
+          4.0,
+          vec![WorkshopItemStateEnum::Subscribed, WorkshopItemStateEnum::DisabledLocally],
+          None,
+          0,
+        ),
+      ],
+    };
+    let diff = diff_manifests(previous, current, |_| true);
+
+    assert_eq!(ids(&diff.broken), vec![1.0, 4.0]);
+  }
+
+  #[test]
+  fn broken_when_the_install_folder_is_missing() {
+    let previous = saved(r#"{"items": []}"#);
+    let mut no_folder = installed(2.0, 10);
+    no_folder.folder = None;
+    let current = WorkshopSubscriptionManifest {
+      items: vec![installed(1.0, 10), no_folder],
+    };
+    let diff = diff_manifests(previous, current, |folder| folder != "/workshop/item");
+
+    assert_eq!(ids(&diff.broken), vec![1.0, 2.0]);
+  }
+}
@@ -1,31 +1,69 @@
+mod create_item;
+mod delete_item;
+mod dependencies;
+mod ensure_installed;
+mod events;
+mod favorite_item;
+mod item_extras;
+mod item_iterator;
+mod item_update;
+mod load_order;
+mod manifest;
+mod overlay;
+mod query_all;
 mod query_item;
+mod query_items;
 mod query_user;
 mod subscribe_item;
 mod types;
 mod query;
+mod submit_item_update;
+mod suspend_downloads;
 mod unsubscribe_item;
+mod vote_item;
 
+use create_item::WorkshopCreateItemTask;
+use delete_item::WorkshopDeleteItemTask;
+use dependencies::{WorkshopAppDependencyTask, WorkshopGetAppDependenciesTask, WorkshopItemChildTask};
+use events::{WorkshopEventCallback, WorkshopEvents};
+use favorite_item::WorkshopFavoriteItemTask;
+use item_iterator::WorkshopItemIterator;
+use item_update::WorkshopItemUpdate;
+use load_order::WorkshopResolveLoadOrderTask;
 use napi::bindgen_prelude::*;
+use napi::{Env, JsObject, JsUnknown};
+use query_all::WorkshopQueryAllTask;
 use query_user::WorkshopQueryUserTask;
 
 use std::sync::Arc;
-use steamworks::{AccountId, AppIDs, AppId, Client, UserList};
+use steamworks::{AccountId, AppIDs, AppId, Client, PublishedFileId, UserList};
+use steamworks_sys as sys;
+use submit_item_update::WorkshopSubmitItemUpdateTask;
 use subscribe_item::WorkshopSubscribeItemTask;
 use types::*;
 use unsubscribe_item::WorkshopUnsubscribeItemTask;
+use vote_item::{WorkshopGetItemVoteTask, WorkshopSetItemVoteTask};
 
 use query_item::WorkshopQueryItemTask;
+use query_items::WorkshopQueryItemsTask;
+
+use crate::steam_api;
 
 // Workshop client for interacting with Steam Workshop
 #[napi]
+#[derive(Clone)]
 pub struct UGCClient {
   client: Arc<Client>,
+  events: Arc<WorkshopEvents>,
 }
 
 #[napi]
 impl UGCClient {
   pub fn new(client: Arc<Client>) -> Self {
-    Self { client: client }
+    Self {
+      client: client,
+      events: Arc::new(WorkshopEvents::default()),
+    }
   }
 
   #[napi]
@@ -38,6 +76,7 @@ impl UGCClient {
       WorkshopItemStateEnum::NeedsUpdate => "NeedsUpdate",
       WorkshopItemStateEnum::Downloading => "Downloading",
       WorkshopItemStateEnum::DownloadPending => "DownloadPending",
+      WorkshopItemStateEnum::DisabledLocally => "DisabledLocally",
     }
   }
 
@@ -49,7 +88,8 @@ impl UGCClient {
     list_type: WorkshopUserListType, 
     item_type: WorkshopUGCType, 
     sort_order: WorkshopUserListOrder, 
-    page: u32
+    page: u32,
+    options: Option<WorkshopQueryOptions>,
   ) -> AsyncTask<WorkshopQueryUserTask> {
     AsyncTask::new(WorkshopQueryUserTask {
       client:self.client.clone(),
@@ -58,15 +98,117 @@ impl UGCClient {
       list_type: list_type.to_user_list(),
       item_type: item_type.to_ugc_type(),
       sort_order: sort_order.to_user_list_order(),
-      page 
+      page,
+      options,
+    })
+  }
+
+  #[napi]
+  pub fn query_all(
+    &self,
+    query_type: WorkshopQueryType,
+    item_type: WorkshopUGCType,
+    creator_app_id: u32,
+    consumer_app_id: Option<u32>,
+    page: u32,
+    options: Option<WorkshopQueryOptions>,
+  ) -> AsyncTask<WorkshopQueryAllTask> {
+    AsyncTask::new(WorkshopQueryAllTask {
+      client: self.client.clone(),
+      query_type: query_type.to_ugc_query_type(),
+      item_type: item_type.to_ugc_type(),
+      app_ids: AppIDs::Both {
+        creator: AppId(creator_app_id),
+        consumer: AppId(consumer_app_id.unwrap_or(creator_app_id)),
+      },
+      page,
+      options,
     })
   }
 
+  // Walks every page of a user query, e.g. `for await (const item of workshop.iterateUser(...))`
+  #[napi(ts_return_type = "AsyncIterable<WorkshopItemDetails>")]
+  pub fn iterate_user(
+    &self,
+    env: Env,
+    app_id: u32,
+    account: u32,
+    list_type: WorkshopUserListType,
+    item_type: WorkshopUGCType,
+    sort_order: WorkshopUserListOrder,
+    options: Option<WorkshopQueryOptions>,
+  ) -> Result<JsObject> {
+    let query = WorkshopQueryUserTask {
+      client: self.client.clone(),
+      app_id: AppId(app_id),
+      account: AccountId::from_raw(account),
+      list_type: list_type.to_user_list(),
+      item_type: item_type.to_ugc_type(),
+      sort_order: sort_order.to_user_list_order(),
+      page: 1,
+      options,
+    };
+    WorkshopItemIterator::create(env, Box::new(query), 1)
+  }
+
+  // Walks every page of an all-items query, see `query_all`
+  #[napi(ts_return_type = "AsyncIterable<WorkshopItemDetails>")]
+  pub fn iterate_all(
+    &self,
+    env: Env,
+    query_type: WorkshopQueryType,
+    item_type: WorkshopUGCType,
+    creator_app_id: u32,
+    consumer_app_id: Option<u32>,
+    options: Option<WorkshopQueryOptions>,
+  ) -> Result<JsObject> {
+    let query = WorkshopQueryAllTask {
+      client: self.client.clone(),
+      query_type: query_type.to_ugc_query_type(),
+      item_type: item_type.to_ugc_type(),
+      app_ids: AppIDs::Both {
+        creator: AppId(creator_app_id),
+        consumer: AppId(consumer_app_id.unwrap_or(creator_app_id)),
+      },
+      page: 1,
+      options,
+    };
+    WorkshopItemIterator::create(env, Box::new(query), 1)
+  }
+
   #[napi]
-  pub fn get_item(&self, item_id: f64) -> AsyncTask<WorkshopQueryItemTask> {
+  pub fn get_item(
+    &self,
+    item_id: f64,
+    options: Option<WorkshopQueryOptions>,
+  ) -> AsyncTask<WorkshopQueryItemTask> {
     AsyncTask::new(WorkshopQueryItemTask {
       client: self.client.clone(),
       item_id,
+      options,
+    })
+  }
+
+  #[napi]
+  pub fn get_items(
+    &self,
+    item_ids: Vec<f64>,
+    options: Option<WorkshopQueryOptions>,
+  ) -> AsyncTask<WorkshopQueryItemsTask> {
+    AsyncTask::new(WorkshopQueryItemsTask {
+      client: self.client.clone(),
+      item_ids,
+      options,
+    })
+  }
+
+  // Sorts the given items (usually the subscriptions) so each loads after its dependencies,
+  // reporting dependency cycles and dependencies that aren't among them
+  #[napi]
+  pub fn resolve_load_order(&self, item_ids: Vec<f64>) -> AsyncTask<WorkshopResolveLoadOrderTask> {
+    AsyncTask::new(WorkshopResolveLoadOrderTask {
+      client: self.client.clone(),
+      item_ids,
     })
   }
 
@@ -86,6 +228,151 @@ impl UGCClient {
     })
   }
 
+  #[napi]
+  pub fn delete_item(&self, item_id: f64) -> AsyncTask<WorkshopDeleteItemTask> {
+    AsyncTask::new(WorkshopDeleteItemTask {
+      client: self.client.clone(),
+      item_id,
+    })
+  }
+
+  #[napi]
+  pub fn set_item_vote(&self, item_id: f64, vote_up: bool) -> AsyncTask<WorkshopSetItemVoteTask> {
+    AsyncTask::new(WorkshopSetItemVoteTask {
+      client: self.client.clone(),
+      item_id,
+      vote_up,
+    })
+  }
+
+  #[napi]
+  pub fn get_item_vote(&self, item_id: f64) -> AsyncTask<WorkshopGetItemVoteTask> {
+    AsyncTask::new(WorkshopGetItemVoteTask {
+      client: self.client.clone(),
+      item_id,
+    })
+  }
+
+  #[napi]
+  pub fn add_item_to_favorites(&self, item_id: f64) -> AsyncTask<WorkshopFavoriteItemTask> {
+    AsyncTask::new(WorkshopFavoriteItemTask {
+      client: self.client.clone(),
+      item_id,
+      favorite: true,
+    })
+  }
+
+  #[napi]
+  pub fn remove_item_from_favorites(&self, item_id: f64) -> AsyncTask<WorkshopFavoriteItemTask> {
+    AsyncTask::new(WorkshopFavoriteItemTask {
+      client: self.client.clone(),
+      item_id,
+      favorite: false,
+    })
+  }
+
+  // Adds `child_id` to a collection, or as a required item of another item
+  #[napi]
+  pub fn add_item_child(&self, parent_id: f64, child_id: f64) -> AsyncTask<WorkshopItemChildTask> {
+    AsyncTask::new(WorkshopItemChildTask {
+      parent_id,
+      child_id,
+      add: true,
+    })
+  }
+
+  #[napi]
+  pub fn remove_item_child(&self, parent_id: f64, child_id: f64) -> AsyncTask<WorkshopItemChildTask> {
+    AsyncTask::new(WorkshopItemChildTask {
+      parent_id,
+      child_id,
+      add: false,
+    })
+  }
+
+  #[napi]
+  pub fn add_app_dependency(&self, item_id: f64, app_id: u32) -> AsyncTask<WorkshopAppDependencyTask> {
+    AsyncTask::new(WorkshopAppDependencyTask {
+      item_id,
+      app_id,
+      add: true,
+    })
+  }
+
+  #[napi]
+  pub fn remove_app_dependency(&self, item_id: f64, app_id: u32) -> AsyncTask<WorkshopAppDependencyTask> {
+    AsyncTask::new(WorkshopAppDependencyTask {
+      item_id,
+      app_id,
+      add: false,
+    })
+  }
+
+  #[napi]
+  pub fn get_app_dependencies(&self, item_id: f64) -> AsyncTask<WorkshopGetAppDependenciesTask> {
+    AsyncTask::new(WorkshopGetAppDependenciesTask { item_id })
+  }
+
+  #[napi]
+  pub fn create_item(
+    &self,
+    app_id: u32,
+    file_type: WorkshopFileType,
+  ) -> AsyncTask<WorkshopCreateItemTask> {
+    AsyncTask::new(WorkshopCreateItemTask {
+      client: self.client.clone(),
+      app_id: AppId(app_id),
+      file_type: file_type.to_file_type(),
+    })
+  }
+
+  #[napi]
+  pub fn submit_item_update(
+    &self,
+    item_id: f64,
+    details: WorkshopItemUpdateDetails,
+  ) -> AsyncTask<WorkshopSubmitItemUpdateTask> {
+    AsyncTask::new(WorkshopSubmitItemUpdateTask {
+      app_id: self.client.utils().app_id(),
+      item_id,
+      details,
+    })
+  }
+
+  #[napi]
+  pub fn start_item_update(
+    &self,
+    item_id: f64,
+    details: WorkshopItemUpdateDetails,
+  ) -> Result<WorkshopItemUpdate> {
+    WorkshopItemUpdate::submit(self.client.utils().app_id(), item_id, &details)
+  }
+
+  // Calls `callback` whenever a subscribed item finishes installing or updating
+  #[napi]
+  pub fn on_item_installed(&self, env: Env, mut callback: WorkshopEventCallback) -> Result<u32> {
+    // Listeners shouldn't keep the Node process alive on their own
+    callback.unref(&env)?;
+    Ok(self.events.on_item_installed(&self.client, callback))
+  }
+
+  // Calls `callback` whenever a download started with `download_item` finishes
+  #[napi]
+  pub fn on_download_item_result(&self, env: Env, mut callback: WorkshopEventCallback) -> Result<u32> {
+    callback.unref(&env)?;
+    Ok(self.events.on_download_item_result(&self.client, callback))
+  }
+
+  #[napi]
+  pub fn remove_listener(&self, listener_id: u32) -> bool {
+    self.events.remove(listener_id)
+  }
+
+  #[napi]
+  pub fn remove_all_listeners(&self) {
+    self.events.remove_all()
+  }
+
   #[napi]
   pub fn get_subscriptions(&self) -> Result<Vec<f64>> {
     let ugc = self.client.ugc();
@@ -95,6 +382,18 @@ impl UGCClient {
     Ok(item_ids)
   }
 
+  // Records the state and install info of every subscribed item, suitable for saving as JSON
+  #[napi]
+  pub fn get_subscription_manifest(&self) -> WorkshopSubscriptionManifest {
+    manifest::snapshot(&self.client)
+  }
+
+  // Compares the current subscriptions against a manifest saved with `JSON.stringify`
+  #[napi]
+  pub fn diff_subscription_manifest(&self, previous_json: String) -> Result<WorkshopManifestDiff> {
+    manifest::diff(&self.client, &previous_json)
+  }
+
   #[napi]
   pub fn get_install_info(&self, item_id: f64) -> Result<Option<WorkshopItemInstallInfo>> {
     let ugc = self.client.ugc();
@@ -132,47 +431,86 @@ impl UGCClient {
     Ok(ugc.download_item(file_id, high_priority))
   }
 
+  // Subscribes to and downloads the item if needed, resolving once it is installed and up to date
   #[napi]
-  pub fn get_item_state(&self, item_id: f64) -> Result<u32> {
-    let ugc = self.client.ugc();
-    let file_id = steamworks::PublishedFileId(item_id as u64);
-    let state = ugc.item_state(file_id);
-    Ok(state.bits())
+  pub async fn ensure_installed(
+    &self,
+    item_id: f64,
+    options: Option<EnsureInstalledOptions>,
+  ) -> Result<WorkshopItemInstallInfo> {
+    ensure_installed::ensure_installed(self.client.clone(), self.events.clone(), item_id, options).await
   }
 
+  // Opens the workshop legal agreement in the Steam overlay, e.g. when `needsToAcceptAgreement` is set.
+  // `overlayEnabled` is false when nothing was shown, so the url can be presented some other way.
   #[napi]
-  pub fn get_item_states(&self, item_id: f64) -> Result<Vec<WorkshopItemStateEnum>> {
-    let ugc = self.client.ugc();
-    let file_id = steamworks::PublishedFileId(item_id as u64);
+  pub fn show_legal_agreement(&self) -> WorkshopOverlayResult {
+    overlay::open_in_overlay(&self.client, overlay::legal_agreement_url())
+  }
 
-    let state = ugc.item_state(file_id);
-    let mut states = Vec::new();
+  // Opens the community page of an item in the Steam overlay
+  #[napi]
+  pub fn show_item_page(&self, item_id: f64) -> WorkshopOverlayResult {
+    overlay::open_in_overlay(&self.client, overlay::item_page_url(item_id))
+  }
 
-    // Check each possible state and add to the result vector if set
-    if state.contains(steamworks::ItemState::SUBSCRIBED) {
-      states.push(WorkshopItemStateEnum::Subscribed);
-    }
-    if state.contains(steamworks::ItemState::LEGACY_ITEM) {
-      states.push(WorkshopItemStateEnum::LegacyItem);
-    }
-    if state.contains(steamworks::ItemState::INSTALLED) {
-      states.push(WorkshopItemStateEnum::Installed);
-    }
-    if state.contains(steamworks::ItemState::NEEDS_UPDATE) {
-      states.push(WorkshopItemStateEnum::NeedsUpdate);
-    }
-    if state.contains(steamworks::ItemState::DOWNLOADING) {
-      states.push(WorkshopItemStateEnum::Downloading);
-    }
-    if state.contains(steamworks::ItemState::DOWNLOAD_PENDING) {
-      states.push(WorkshopItemStateEnum::DownloadPending);
-    }
+  // Pauses (or resumes) all workshop downloads, e.g. while a multiplayer match is running
+  #[napi]
+  pub fn suspend_downloads(&self, suspend: bool) {
+    self.client.ugc().suspend_downloads(suspend);
+  }
 
-    // If no states are found, add None
-    if states.is_empty() {
-      states.push(WorkshopItemStateEnum::None);
-    }
+  // Keeps downloads suspended until `promise` settles, then resumes them even if it rejected
+  #[napi(
+    ts_generic_types = "T",
+    ts_args_type = "promise: Promise<T>",
+    ts_return_type = "Promise<T>"
+  )]
+  pub fn while_downloads_suspended(&self, env: Env, promise: JsObject) -> Result<JsUnknown> {
+    suspend_downloads::while_downloads_suspended(env, &self.client, promise)
+  }
 
-    Ok(states)
+  // Starts counting playtime against the given items until stopped
+  #[napi]
+  pub fn start_playtime_tracking(&self, item_ids: Vec<f64>) {
+    let file_ids: Vec<PublishedFileId> = item_ids.iter().map(|id| PublishedFileId(*id as u64)).collect();
+    self.client.ugc().start_playtime_tracking(&file_ids);
+  }
+
+  #[napi]
+  pub fn stop_playtime_tracking(&self, item_ids: Vec<f64>) {
+    let file_ids: Vec<PublishedFileId> = item_ids.iter().map(|id| PublishedFileId(*id as u64)).collect();
+    self.client.ugc().stop_playtime_tracking(&file_ids);
+  }
+
+  #[napi]
+  pub fn stop_playtime_tracking_for_all_items(&self) {
+    self.client.ugc().stop_playtime_tracking_for_all_items();
+  }
+
+  #[napi]
+  pub fn get_item_state(&self, item_id: f64) -> Result<ItemStateFlags> {
+    Ok(ItemStateFlags::from_raw_state(raw_item_state(item_id as u64)))
   }
+
+  // Same as `get_item_state` for many items at once, in the order given
+  #[napi]
+  pub fn get_item_state_bulk(&self, item_ids: Vec<f64>) -> Result<Vec<ItemStateFlags>> {
+    Ok(
+      item_ids
+        .iter()
+        .map(|id| ItemStateFlags::from_raw_state(raw_item_state(*id as u64)))
+        .collect(),
+    )
+  }
+
+  #[napi]
+  pub fn get_item_states(&self, item_id: f64) -> Result<Vec<WorkshopItemStateEnum>> {
+    Ok(WorkshopItemStateEnum::from_raw_state(raw_item_state(item_id as u64)))
+  }
+}
+
+// EItemState bits for an item, including the ones steamworks-rs' ItemState drops
+pub(crate) fn raw_item_state(item_id: u64) -> u32 {
+  unsafe { sys::SteamAPI_ISteamUGC_GetItemState(steam_api::ugc(), item_id) }
 }
@@ -0,0 +1,24 @@
+use steamworks_sys as sys;
+
+use super::types::WorkshopOverlayResult;
+use crate::steam_api;
+
+const LEGAL_AGREEMENT_URL: &str = "https://steamcommunity.com/sharedfiles/workshoplegalagreement";
+
+pub fn legal_agreement_url() -> String {
+  LEGAL_AGREEMENT_URL.to_string()
+}
+
+pub fn item_page_url(item_id: f64) -> String {
+  format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}", item_id as u64)
+}
+
+/// Opens `url` in the Steam overlay if it's enabled, otherwise just hands the url back
+pub fn open_in_overlay(client: &steamworks::Client, url: String) -> WorkshopOverlayResult {
+  let overlay_enabled = unsafe { sys::SteamAPI_ISteamUtils_IsOverlayEnabled(steam_api::utils()) };
+  if overlay_enabled {
+    client.friends().activate_game_overlay_to_web_page(&url);
+  }
+
+  WorkshopOverlayResult { overlay_enabled, url }
+}
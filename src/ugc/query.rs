@@ -1,36 +1,185 @@
 use napi::Error;
 use napi::Result;
 use std::sync::mpsc;
-use steamworks::QueryHandle;
+use steamworks::{QueryHandle, QueryResults, UGCStatisticType};
 
+use super::item_extras::fetch_item_extras;
 use super::types::*;
 
+// Steam returns at most this many items per UGC query (kNumUGCResultsPerPage)
+pub const RESULTS_PER_PAGE: u32 = 50;
+
+const DEFAULT_PLAYTIME_STATS_DAYS: u32 = 30;
+
+/// A workshop query that can be re-created for any page of its results
+pub trait PagedQuery: Send {
+  fn create_query_handle(&self, page: u32) -> Result<QueryHandle>;
+  fn options(&self) -> &Option<WorkshopQueryOptions>;
+}
+
 // /// Async Task for getting workshop item details
-pub fn fetch_details(query_handle: QueryHandle) -> Result<Vec<WorkshopItemDetails>> {
+pub fn fetch_details(
+  query_handle: QueryHandle,
+  options: &Option<WorkshopQueryOptions>,
+) -> Result<Vec<WorkshopItemDetails>> {
+  fetch_page(query_handle, options, 1).map(|page| page.items)
+}
+
+/// Fetches one page of results along with the totals needed to page through the rest
+pub fn fetch_page(
+  query_handle: QueryHandle,
+  options: &Option<WorkshopQueryOptions>,
+  page: u32,
+) -> Result<WorkshopItemPage> {
   let (tx, rx) = mpsc::channel();
+  let include_children = options
+    .as_ref()
+    .and_then(|o| o.include_children)
+    .unwrap_or(false);
+  let include_statistics = options
+    .as_ref()
+    .and_then(|o| o.include_statistics)
+    .unwrap_or(false);
 
   query_handle.fetch(move |result| {
     let _ = match result {
-      Ok(qresults) => tx.send(Ok(query_result_to_details(qresults))),
+      Ok(qresults) => {
+        let total_results = qresults.total_results();
+        let returned_results = qresults.returned_results();
+        tx.send(Ok((
+          query_result_to_details(qresults, include_children, include_statistics),
+          total_results,
+          returned_results,
+        )))
+      }
       Err(e) => tx.send(Err(e.to_string())),
     };
   });
 
-  match rx.recv() {
-    Ok(Ok(result)) => Ok(result),
-    Ok(Err(e)) => Err(Error::from_reason(e)),
-    Err(e) => Err(Error::from_reason(format!(
-      r"Steamworks: Failed to receive workshop item details: {:?}",
-      e
-    ))),
+  let (items, total_results, returned_results) = match rx.recv() {
+    Ok(Ok(result)) => result,
+    Ok(Err(e)) => return Err(Error::from_reason(e)),
+    Err(e) => {
+      return Err(Error::from_reason(format!(
+        r"Steamworks: Failed to receive workshop item details: {:?}",
+        e
+      )))
+    }
+  };
+
+  let items = match options {
+    Some(options) if options.wants_extras() && !items.is_empty() => {
+      merge_item_extras(items, options)?
+    }
+    _ => items,
+  };
+
+  Ok(WorkshopItemPage {
+    items,
+    total_results,
+    returned_results,
+    next_cursor: next_cursor(page, returned_results, total_results),
+  })
+}
+
+// The page to fetch after `page`, or None once every result has been seen.
+// Page numbers come from JS, so a huge one just ends the paging instead of overflowing.
+fn next_cursor(page: u32, returned_results: u32, total_results: u32) -> Option<u32> {
+  let page = page.max(1);
+  let seen = (page - 1)
+    .saturating_mul(RESULTS_PER_PAGE)
+    .saturating_add(returned_results);
+  if returned_results == 0 || seen >= total_results {
+    return None;
+  }
+  page.checked_add(1)
+}
+
+/// Applies every filter set in `options` to a query handle
+pub fn apply_query_options(
+  mut query_handle: QueryHandle,
+  options: &Option<WorkshopQueryOptions>,
+) -> QueryHandle {
+  let options = match options {
+    Some(options) => options,
+    None => return query_handle,
+  };
+
+  if let Some(tags) = &options.required_tags {
+    for tag in tags {
+      query_handle = query_handle.require_tag(tag);
+    }
+  }
+  if let Some(match_any_tag) = options.match_any_tag {
+    query_handle = query_handle.any_required(match_any_tag);
+  }
+  if let Some(tags) = &options.excluded_tags {
+    for tag in tags {
+      query_handle = query_handle.exclude_tag(tag);
+    }
+  }
+  if let Some(kv_tags) = &options.required_key_value_tags {
+    for kv in kv_tags {
+      query_handle = query_handle.add_required_key_value_tag(&kv.key, &kv.value);
+    }
+  }
+  if let Some(search_text) = &options.search_text {
+    query_handle = query_handle.set_search_text(search_text);
   }
+  if let Some(days) = options.ranked_by_trend_days {
+    query_handle = query_handle.set_ranked_by_trend_days(days);
+  }
+  if let Some(language) = &options.language {
+    query_handle = query_handle.language(language);
+  }
+  if let Some(max_age) = options.cache_max_age_seconds {
+    query_handle = query_handle.allow_cached_response(max_age);
+  }
+  if let Some(include_children) = options.include_children {
+    query_handle = query_handle.include_children(include_children);
+  }
+  // Steam leaves the playtime statistics empty unless they are asked for explicitly
+  if options.include_statistics.unwrap_or(false) {
+    query_handle = query_handle.set_return_playtime_stats(
+      options.playtime_stats_days.unwrap_or(DEFAULT_PLAYTIME_STATS_DAYS),
+    );
+  }
+
+  query_handle
 }
 
-fn query_result_to_details(qresults: steamworks::QueryResults) -> Vec<WorkshopItemDetails> {
+// Fills in the data that has to come from a separate details request.
+// steamworks-rs keeps the raw query handle private and has no getters for metadata, key-value
+// tags or additional previews, so they can't be read from the original `QueryResults`. Once swrs
+// exposes them, set the flags in `apply_query_options` and read them in `query_result_to_details`.
+fn merge_item_extras(
+  mut details: Vec<WorkshopItemDetails>,
+  options: &WorkshopQueryOptions,
+) -> Result<Vec<WorkshopItemDetails>> {
+  let item_ids = details.iter().map(|d| d.item_id as u64).collect::<Vec<_>>();
+  let mut extras = fetch_item_extras(&item_ids, options)?;
+
+  for item in details.iter_mut() {
+    if let Some(extra) = extras.remove(&(item.item_id as u64)) {
+      item.metadata = extra.metadata;
+      item.key_value_tags = extra.key_value_tags;
+      item.additional_previews = extra.additional_previews;
+    }
+  }
+
+  Ok(details)
+}
+
+fn query_result_to_details(
+  qresults: QueryResults,
+  include_children: bool,
+  include_statistics: bool,
+) -> Vec<WorkshopItemDetails> {
   qresults
     .iter()
-    .flatten()
-    .map(|qr| WorkshopItemDetails {
+    .enumerate()
+    .filter_map(|(index, qr)| qr.map(|qr| (index as u32, qr)))
+    .map(|(index, qr)| WorkshopItemDetails {
       item_id: qr.published_file_id.0 as f64,
       title: qr.title.clone(),
       description: qr.description.clone(),
@@ -49,6 +198,80 @@ fn query_result_to_details(qresults: steamworks::QueryResults) -> Vec<WorkshopIt
       num_downvotes: qr.num_downvotes,
       score: qr.score as f64,
       num_children: qr.num_children,
+      preview_url: qresults.preview_url(index),
+      metadata: None,
+      key_value_tags: None,
+      additional_previews: None,
+      children: if include_children {
+        qresults
+          .get_children(index)
+          .map(|children| children.into_iter().map(|id| id as f64).collect())
+      } else {
+        None
+      },
+      statistics: if include_statistics {
+        Some(query_result_statistics(&qresults, index))
+      } else {
+        None
+      },
     })
     .collect()
 }
+
+fn query_result_statistics(qresults: &QueryResults, index: u32) -> WorkshopItemStatistics {
+  let stat = |stat_type| qresults.statistic(index, stat_type).map(|value| value as f64);
+
+  WorkshopItemStatistics {
+    subscriptions: stat(UGCStatisticType::Subscriptions),
+    favorites: stat(UGCStatisticType::Favorites),
+    followers: stat(UGCStatisticType::Followers),
+    unique_subscriptions: stat(UGCStatisticType::UniqueSubscriptions),
+    unique_favorites: stat(UGCStatisticType::UniqueFavorites),
+    unique_followers: stat(UGCStatisticType::UniqueFollowers),
+    unique_website_views: stat(UGCStatisticType::UniqueWebsiteViews),
+    seconds_played: stat(UGCStatisticType::SecondsPlayed),
+    playtime_sessions: stat(UGCStatisticType::PlaytimeSessions),
+    comments: stat(UGCStatisticType::Comments),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn next_cursor_moves_to_the_next_page_while_results_remain() {
+    assert_eq!(next_cursor(1, 50, 120), Some(2));
+    assert_eq!(next_cursor(2, 50, 120), Some(3));
+  }
+
+  #[test]
+  fn next_cursor_ends_on_the_last_page() {
+    assert_eq!(next_cursor(3, 20, 120), None);
+    assert_eq!(next_cursor(1, 10, 10), None);
+  }
+
+  #[test]
+  fn next_cursor_ends_on_an_empty_page() {
+    assert_eq!(next_cursor(1, 0, 0), None);
+    // Steam can report more results than it hands out
+    assert_eq!(next_cursor(4, 0, 500), None);
+  }
+
+  #[test]
+  fn next_cursor_ends_on_an_exact_multiple_of_the_page_size() {
+    assert_eq!(next_cursor(1, 50, 100), Some(2));
+    assert_eq!(next_cursor(2, 50, 100), None);
+  }
+
+  #[test]
+  fn next_cursor_treats_page_zero_as_the_first_page() {
+    assert_eq!(next_cursor(0, 50, 100), Some(2));
+  }
+
+  #[test]
+  fn next_cursor_does_not_overflow_on_huge_pages() {
+    assert_eq!(next_cursor(u32::MAX, 50, u32::MAX), None);
+    assert_eq!(next_cursor(u32::MAX / RESULTS_PER_PAGE + 2, 1, 100), None);
+  }
+}
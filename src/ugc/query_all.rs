@@ -0,0 +1,53 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, Result};
+use std::sync::Arc;
+use steamworks::{AppIDs, QueryHandle, UGCQueryType, UGCType};
+
+use super::query::{apply_query_options, fetch_page, PagedQuery};
+use super::types::*;
+
+/// Async Task for browsing all workshop items of an app
+pub struct WorkshopQueryAllTask {
+  pub client: Arc<steamworks::Client>,
+  pub query_type: UGCQueryType,
+  pub item_type: UGCType,
+  pub app_ids: AppIDs,
+  pub page: u32,
+  pub options: Option<WorkshopQueryOptions>,
+}
+
+impl PagedQuery for WorkshopQueryAllTask {
+  fn create_query_handle(&self, page: u32) -> Result<QueryHandle> {
+    match self
+      .client
+      .ugc()
+      .query_all(self.query_type, self.item_type, self.app_ids, page)
+    {
+      Ok(handle) => Ok(handle),
+      Err(e) => Err(Error::from_reason(e.to_string())),
+    }
+  }
+
+  fn options(&self) -> &Option<WorkshopQueryOptions> {
+    &self.options
+  }
+}
+
+#[napi]
+impl Task for WorkshopQueryAllTask {
+  type Output = WorkshopItemPage;
+  type JsValue = WorkshopItemPage;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let query_handle = apply_query_options(self.create_query_handle(self.page)?, &self.options);
+    fetch_page(query_handle, &self.options, self.page)
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
@@ -3,13 +3,14 @@ use napi::{Env, Result};
 use std::sync::Arc;
 use steamworks::{PublishedFileId, QueryHandle};
 
-use super::query::fetch_details;
-use super::types::WorkshopItemDetails;
+use super::query::{apply_query_options, fetch_details};
+use super::types::{WorkshopItemDetails, WorkshopQueryOptions};
 
 /// Async Task for getting workshop item details
 pub struct WorkshopQueryItemTask {
   pub client: Arc<steamworks::Client>,
   pub item_id: f64,
+  pub options: Option<WorkshopQueryOptions>,
 }
 
 impl WorkshopQueryItemTask {
@@ -27,10 +28,10 @@ impl Task for WorkshopQueryItemTask {
   type JsValue = Option<WorkshopItemDetails>;
 
   fn compute(&mut self) -> napi::Result<Self::Output> {
-    let query_handle = self.create_query_handle()?;
-    let details = fetch_details(query_handle)?;
-    let first = details.into_iter().nth(0).unwrap();
-    Ok(Some(first))
+    let query_handle = apply_query_options(self.create_query_handle()?, &self.options);
+    let details = fetch_details(query_handle, &self.options)?;
+    // Filters in `options` can leave nothing to return
+    Ok(details.into_iter().next())
   }
 
   fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
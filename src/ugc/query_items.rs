@@ -0,0 +1,117 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+use steamworks::PublishedFileId;
+use steamworks_sys as sys;
+
+use super::item_extras::fetch_item_results;
+use super::query::{apply_query_options, fetch_details, RESULTS_PER_PAGE};
+use super::types::*;
+use crate::errors::steam_error_to_napi;
+use crate::steam_api;
+
+/// Async Task for getting workshop item details for many items at once
+pub struct WorkshopQueryItemsTask {
+  pub client: Arc<steamworks::Client>,
+  pub item_ids: Vec<f64>,
+  pub options: Option<WorkshopQueryOptions>,
+}
+
+impl WorkshopQueryItemsTask {
+  fn fetch_batch(&self, batch: &[f64]) -> Result<Vec<WorkshopItemDetails>> {
+    let ids = batch
+      .iter()
+      .map(|id| PublishedFileId(*id as u64))
+      .collect::<Vec<_>>();
+    let query_handle = match self.client.ugc().query_items(ids) {
+      Ok(handle) => handle,
+      Err(e) => return Err(Error::from_reason(e.to_string())),
+    };
+    fetch_details(apply_query_options(query_handle, &self.options), &self.options)
+  }
+}
+
+// Why an item couldn't be looked up, from the result Steam gave for it if there was one
+fn lookup_error(result: Option<sys::EResult>) -> String {
+  match result.map(steam_api::check_result) {
+    Some(Err(e)) => steam_error_to_napi(e).reason,
+    _ => "Steamworks: File not found".to_string(),
+  }
+}
+
+#[napi]
+impl Task for WorkshopQueryItemsTask {
+  type Output = Vec<WorkshopItemLookup>;
+  type JsValue = Vec<WorkshopItemLookup>;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let mut lookups = Vec::with_capacity(self.item_ids.len());
+
+    for batch in self.item_ids.chunks(RESULTS_PER_PAGE as usize) {
+      let found = match self.fetch_batch(batch) {
+        Ok(details) => details
+          .into_iter()
+          .map(|d| (d.item_id as u64, d))
+          .collect::<HashMap<_, _>>(),
+        Err(e) => {
+          lookups.extend(batch.iter().map(|id| WorkshopItemLookup {
+            item_id: *id,
+            details: None,
+            error: Some(e.reason.clone()),
+          }));
+          continue;
+        }
+      };
+
+      // steamworks-rs leaves out items Steam didn't return OK for, so ask Steam why.
+      // If that fails as well the items are still reported, just without the reason.
+      let mut missing = batch
+        .iter()
+        .map(|id| *id as u64)
+        .filter(|id| !matches!(found.get(id), Some(details) if details.time_created != 0))
+        .collect::<Vec<_>>();
+      missing.sort_unstable();
+      missing.dedup();
+      let results = if missing.is_empty() {
+        HashMap::new()
+      } else {
+        fetch_item_results(&missing).unwrap_or_default()
+      };
+
+      // The same id can be asked for more than once, so the details are cloned rather than taken
+      for id in batch {
+        let item_id = *id as u64;
+        let lookup = match found.get(&item_id) {
+          Some(details) if details.banned => WorkshopItemLookup {
+            item_id: *id,
+            details: Some(details.clone()),
+            error: Some("Steamworks: Item is banned".to_string()),
+          },
+          // Unknown ids can still come back as a result, just with nothing filled in
+          Some(details) if details.time_created != 0 => WorkshopItemLookup {
+            item_id: *id,
+            details: Some(details.clone()),
+            error: None,
+          },
+          _ => WorkshopItemLookup {
+            item_id: *id,
+            details: None,
+            error: Some(lookup_error(results.get(&item_id).copied())),
+          },
+        };
+        lookups.push(lookup);
+      }
+    }
+
+    Ok(lookups)
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
@@ -3,7 +3,7 @@ use napi::{Env, Result};
 use std::sync::Arc;
 use steamworks::{AccountId, AppIDs, AppId, QueryHandle, UGCType, UserList, UserListOrder};
 
-use super::query::fetch_details;
+use super::query::{apply_query_options, fetch_page, PagedQuery};
 use super::types::*;
 
 // /// Async Task for getting workshop item details
@@ -15,10 +15,11 @@ pub struct WorkshopQueryUserTask {
   pub item_type: UGCType,
   pub sort_order: UserListOrder,
   pub page: u32,
+  pub options: Option<WorkshopQueryOptions>,
 }
 
-impl WorkshopQueryUserTask {
-  fn create_query_handle(&self) -> Result<QueryHandle> {
+impl PagedQuery for WorkshopQueryUserTask {
+  fn create_query_handle(&self, page: u32) -> Result<QueryHandle> {
     match self.client.ugc().query_user(
       self.account,
       self.list_type,
@@ -28,23 +29,26 @@ impl WorkshopQueryUserTask {
         creator: self.app_id,
         consumer: self.app_id,
       },
-      self.page,
+      page,
     ) {
       Ok(handle) => Ok(handle),
       Err(e) => Err(Error::from_reason(e.to_string())),
     }
   }
+
+  fn options(&self) -> &Option<WorkshopQueryOptions> {
+    &self.options
+  }
 }
 
 #[napi]
 impl Task for WorkshopQueryUserTask {
-  type Output = Option<Vec<WorkshopItemDetails>>;
-  type JsValue = Option<Vec<WorkshopItemDetails>>;
+  type Output = WorkshopItemPage;
+  type JsValue = WorkshopItemPage;
 
   fn compute(&mut self) -> napi::Result<Self::Output> {
-    let query_handle = self.create_query_handle()?;
-    let details = fetch_details(query_handle)?;
-    Ok(Some(details))
+    let query_handle = apply_query_options(self.create_query_handle(self.page)?, &self.options);
+    fetch_page(query_handle, &self.options, self.page)
   }
 
   fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
@@ -0,0 +1,297 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, Error, Result};
+use std::ffi::{c_char, CString};
+use std::ptr;
+use std::sync::mpsc;
+use steamworks::AppId;
+use steamworks_sys as sys;
+
+use super::item_extras::METADATA_MAX;
+use super::types::{WorkshopItemCreationResult, WorkshopItemUpdateDetails};
+use crate::errors::steam_error_to_napi;
+use crate::steam_api;
+
+// The update is built on the flat API because steamworks-rs keeps the raw
+// UGCUpdateHandle_t private, and preview management needs it. Everything goes through the
+// call result pipe, so the handle and the submit result live on the same pipe.
+
+fn to_c_string(value: &str, field: &str) -> Result<CString> {
+  CString::new(value).map_err(|_| {
+    Error::from_reason(format!("Steamworks: Item {} must not contain NUL bytes", field))
+  })
+}
+
+fn check_set(ok: bool, field: &str) -> Result<()> {
+  if ok {
+    Ok(())
+  } else {
+    Err(Error::from_reason(format!("Steamworks: Failed to set item {}", field)))
+  }
+}
+
+/// Starts an update for `item_id` with every field set in `details` applied
+pub fn begin_item_update(
+  app_id: AppId,
+  item_id: f64,
+  details: &WorkshopItemUpdateDetails,
+) -> Result<sys::UGCUpdateHandle_t> {
+  let ugc = steam_api::call_ugc();
+  let handle = unsafe { sys::SteamAPI_ISteamUGC_StartItemUpdate(ugc, app_id.0, item_id as u64) };
+  if handle == u64::MAX {
+    return Err(Error::from_reason(format!(
+      "Steamworks: Failed to start update of item {}",
+      item_id as u64
+    )));
+  }
+
+  if let Some(title) = &details.title {
+    let title = to_c_string(title, "title")?;
+    check_set(unsafe { sys::SteamAPI_ISteamUGC_SetItemTitle(ugc, handle, title.as_ptr()) }, "title")?;
+  }
+  if let Some(description) = &details.description {
+    let description = to_c_string(description, "description")?;
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_SetItemDescription(ugc, handle, description.as_ptr()) },
+      "description",
+    )?;
+  }
+  if let Some(preview_path) = &details.preview_path {
+    let preview_path = to_c_string(preview_path, "preview path")?;
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_SetItemPreview(ugc, handle, preview_path.as_ptr()) },
+      "preview",
+    )?;
+  }
+  if let Some(content_path) = &details.content_path {
+    let content_path = to_c_string(content_path, "content path")?;
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_SetItemContent(ugc, handle, content_path.as_ptr()) },
+      "content",
+    )?;
+  }
+  if let Some(tags) = &details.tags {
+    let tags = tags
+      .iter()
+      .map(|tag| to_c_string(tag, "tag"))
+      .collect::<Result<Vec<_>>>()?;
+    let mut tag_ptrs = tags.iter().map(|tag| tag.as_ptr()).collect::<Vec<*const c_char>>();
+    let tag_array = sys::SteamParamStringArray_t {
+      m_ppStrings: tag_ptrs.as_mut_ptr(),
+      m_nNumStrings: tag_ptrs.len() as i32,
+    };
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_SetItemTags(ugc, handle, &tag_array, false) },
+      "tags",
+    )?;
+  }
+  if let Some(visibility) = &details.visibility {
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_SetItemVisibility(ugc, handle, visibility.to_raw_visibility()) },
+      "visibility",
+    )?;
+  }
+
+  if let Some(metadata) = &details.metadata {
+    if metadata.len() > METADATA_MAX {
+      return Err(Error::from_reason(format!(
+        "Steamworks: Item metadata must be at most {} bytes",
+        METADATA_MAX
+      )));
+    }
+    let metadata = to_c_string(metadata, "metadata")?;
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_SetItemMetadata(ugc, handle, metadata.as_ptr()) },
+      "metadata",
+    )?;
+  }
+
+  apply_key_value_tag_changes(ugc, handle, details)?;
+  apply_preview_changes(ugc, handle, details)?;
+
+  Ok(handle)
+}
+
+// Removals go first so a key can be replaced by removing and re-adding it in one update
+fn apply_key_value_tag_changes(
+  ugc: *mut sys::ISteamUGC,
+  handle: sys::UGCUpdateHandle_t,
+  details: &WorkshopItemUpdateDetails,
+) -> Result<()> {
+  for key in details.remove_key_value_tags.iter().flatten() {
+    let key = to_c_string(key, "key-value tag key")?;
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_RemoveItemKeyValueTags(ugc, handle, key.as_ptr()) },
+      "key-value tag removal",
+    )?;
+  }
+  for tag in details.add_key_value_tags.iter().flatten() {
+    let key = to_c_string(&tag.key, "key-value tag key")?;
+    let value = to_c_string(&tag.value, "key-value tag value")?;
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_AddItemKeyValueTag(ugc, handle, key.as_ptr(), value.as_ptr()) },
+      "key-value tag",
+    )?;
+  }
+
+  Ok(())
+}
+
+// Replacements go first and removals run from the highest index down, so the
+// indices given by the caller (as returned from a query) stay valid throughout.
+// New previews are appended last.
+fn apply_preview_changes(
+  ugc: *mut sys::ISteamUGC,
+  handle: sys::UGCUpdateHandle_t,
+  details: &WorkshopItemUpdateDetails,
+) -> Result<()> {
+  for replacement in details.replace_previews.iter().flatten() {
+    match (&replacement.file, &replacement.video_id) {
+      (Some(file), None) => {
+        let file = to_c_string(file, "preview file")?;
+        check_set(
+          unsafe {
+            sys::SteamAPI_ISteamUGC_UpdateItemPreviewFile(ugc, handle, replacement.index, file.as_ptr())
+          },
+          "preview file",
+        )?;
+      }
+      (None, Some(video_id)) => {
+        let video_id = to_c_string(video_id, "preview video")?;
+        check_set(
+          unsafe {
+            sys::SteamAPI_ISteamUGC_UpdateItemPreviewVideo(ugc, handle, replacement.index, video_id.as_ptr())
+          },
+          "preview video",
+        )?;
+      }
+      _ => {
+        return Err(Error::from_reason(format!(
+          "Steamworks: Preview replacement {} needs exactly one of file or videoId",
+          replacement.index
+        )))
+      }
+    }
+  }
+
+  let mut removals = details.remove_previews.clone().unwrap_or_default();
+  removals.sort_unstable_by(|a, b| b.cmp(a));
+  removals.dedup();
+  for index in removals {
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_RemoveItemPreview(ugc, handle, index) },
+      "preview removal",
+    )?;
+  }
+
+  for file in details.add_preview_files.iter().flatten() {
+    let file = to_c_string(file, "preview file")?;
+    check_set(
+      unsafe {
+        sys::SteamAPI_ISteamUGC_AddItemPreviewFile(
+          ugc,
+          handle,
+          file.as_ptr(),
+          sys::EItemPreviewType::k_EItemPreviewType_Image,
+        )
+      },
+      "preview file",
+    )?;
+  }
+  for video_id in details.add_preview_videos.iter().flatten() {
+    let video_id = to_c_string(video_id, "preview video")?;
+    check_set(
+      unsafe { sys::SteamAPI_ISteamUGC_AddItemPreviewVideo(ugc, handle, video_id.as_ptr()) },
+      "preview video",
+    )?;
+  }
+
+  Ok(())
+}
+
+/// Starts uploading an update built with `begin_item_update`
+pub fn submit_item_update(
+  handle: sys::UGCUpdateHandle_t,
+  changelog: &Option<String>,
+) -> Result<sys::SteamAPICall_t> {
+  let changelog = match changelog {
+    Some(changelog) => Some(to_c_string(changelog, "changelog")?),
+    None => None,
+  };
+  let changelog_ptr = changelog.as_ref().map_or(ptr::null(), |c| c.as_ptr());
+  Ok(unsafe { sys::SteamAPI_ISteamUGC_SubmitItemUpdate(steam_api::call_ugc(), handle, changelog_ptr) })
+}
+
+/// Raw upload status plus bytes processed and total for a submitted update
+pub fn item_update_progress(handle: sys::UGCUpdateHandle_t) -> (sys::EItemUpdateStatus, u64, u64) {
+  let mut processed = 0u64;
+  let mut total = 0u64;
+  let status = unsafe {
+    sys::SteamAPI_ISteamUGC_GetItemUpdateProgress(steam_api::call_ugc(), handle, &mut processed, &mut total)
+  };
+  (status, processed, total)
+}
+
+/// Calls `callback` once Steam has accepted or rejected a submitted update
+pub fn on_item_update_result(
+  call: sys::SteamAPICall_t,
+  callback: impl FnOnce(Result<WorkshopItemCreationResult>) + Send + 'static,
+) {
+  steam_api::register_call_result::<sys::SubmitItemUpdateResult_t>(call, move |result| {
+    callback(match result {
+      Ok(result) => steam_api::check_result(result.m_eResult)
+        .map(|_| WorkshopItemCreationResult {
+          item_id: result.m_nPublishedFileId as f64,
+          needs_to_accept_agreement: result.m_bUserNeedsToAcceptWorkshopLegalAgreement,
+        })
+        .map_err(steam_error_to_napi),
+      Err(e) => Err(Error::from_reason(format!(
+        r"Steamworks: Failed to receive item update result: {}",
+        e
+      ))),
+    })
+  });
+}
+
+/// Async Task for applying and uploading changes to a workshop item
+pub struct WorkshopSubmitItemUpdateTask {
+  pub app_id: AppId,
+  pub item_id: f64,
+  pub details: WorkshopItemUpdateDetails,
+}
+
+impl WorkshopSubmitItemUpdateTask {
+  fn submit_item_update(&self) -> Result<WorkshopItemCreationResult> {
+    let handle = begin_item_update(self.app_id, self.item_id, &self.details)?;
+    let call = submit_item_update(handle, &self.details.changelog)?;
+
+    let (tx, rx) = mpsc::channel();
+    on_item_update_result(call, move |result| {
+      let _ = tx.send(result);
+    });
+    match rx.recv() {
+      Ok(result) => result,
+      Err(e) => Err(Error::from_reason(format!(
+        r"Steamworks: Failed to receive item update result: {:?}",
+        e
+      ))),
+    }
+  }
+}
+
+#[napi]
+impl Task for WorkshopSubmitItemUpdateTask {
+  type Output = WorkshopItemCreationResult;
+  type JsValue = WorkshopItemCreationResult;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.submit_item_update()
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
@@ -0,0 +1,41 @@
+use napi::{CallContext, Env, JsFunction, JsObject, JsUndefined, JsUnknown, Result};
+use std::sync::atomic::{AtomicU32, Ordering};
+use steamworks_sys as sys;
+
+use crate::steam_api;
+
+// Number of `while_downloads_suspended` scopes still waiting on their promise.
+// Downloads only resume once the last of them settles.
+static SUSPENDED_SCOPES: AtomicU32 = AtomicU32::new(0);
+
+/// Suspends downloads until `promise` settles, returning `promise.finally(resume)`
+pub fn while_downloads_suspended(env: Env, client: &steamworks::Client, promise: JsObject) -> Result<JsUnknown> {
+  if SUSPENDED_SCOPES.fetch_add(1, Ordering::SeqCst) == 0 {
+    client.ugc().suspend_downloads(true);
+  }
+
+  let chained = promise
+    .get_named_property::<JsFunction>("finally")
+    .and_then(|finally| {
+      let resume = env.create_function("resumeDownloads", end_suspended_scope)?;
+      finally.call(Some(&promise), &[resume])
+    });
+
+  if chained.is_err() {
+    end_scope();
+  }
+  chained
+}
+
+#[js_function(0)]
+fn end_suspended_scope(ctx: CallContext) -> Result<JsUndefined> {
+  end_scope();
+  ctx.env.get_undefined()
+}
+
+// There's no closure to carry the client here, so resume through the flat API
+fn end_scope() {
+  if SUSPENDED_SCOPES.fetch_sub(1, Ordering::SeqCst) == 1 {
+    unsafe { sys::SteamAPI_ISteamUGC_SuspendDownloads(steam_api::ugc(), false) };
+  }
+}
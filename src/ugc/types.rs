@@ -1,7 +1,10 @@
-use steamworks::{FileType, UGCType, UserList, UserListOrder};
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction};
+use steamworks::{FileType, UGCQueryType, UGCType, UserList, UserListOrder};
+use steamworks_sys as sys;
 
 // Workshop item details
 #[napi(object)]
+#[derive(Clone)]
 pub struct WorkshopItemDetails {
   pub item_id: f64,
   pub title: String,
@@ -21,6 +24,125 @@ pub struct WorkshopItemDetails {
   pub num_downvotes: u32,
   pub score: f64,
   pub num_children: u32,
+  pub preview_url: Option<String>,
+  pub metadata: Option<String>,
+  pub key_value_tags: Option<Vec<WorkshopKeyValueTag>>,
+  pub additional_previews: Option<Vec<WorkshopItemPreview>>,
+  pub children: Option<Vec<f64>>,
+  pub statistics: Option<WorkshopItemStatistics>,
+}
+
+// Workshop item preview type
+#[napi]
+pub enum WorkshopItemPreviewType {
+  Image = 0,
+  YouTubeVideo = 1,
+  Sketchfab = 2,
+  EnvironmentMapHorizontalCross = 3,
+  EnvironmentMapLatLong = 4,
+  Clip = 5,
+}
+
+impl WorkshopItemPreviewType {
+    pub const fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(WorkshopItemPreviewType::Image),
+            1 => Some(WorkshopItemPreviewType::YouTubeVideo),
+            2 => Some(WorkshopItemPreviewType::Sketchfab),
+            3 => Some(WorkshopItemPreviewType::EnvironmentMapHorizontalCross),
+            4 => Some(WorkshopItemPreviewType::EnvironmentMapLatLong),
+            5 => Some(WorkshopItemPreviewType::Clip),
+            _ => None,
+        }
+    }
+}
+
+// Additional preview (image, video, ...) attached to a workshop item
+#[napi(object)]
+#[derive(Clone)]
+pub struct WorkshopItemPreview {
+  pub index: u32,
+  pub preview_type: WorkshopItemPreviewType,
+  pub url_or_video_id: String,
+  pub original_file_name: Option<String>,
+}
+
+// Workshop item statistics
+#[napi(object)]
+#[derive(Clone)]
+pub struct WorkshopItemStatistics {
+  // Left out when Steam didn't return the statistic
+  pub subscriptions: Option<f64>, // Using f64 instead of u64 for JS compatibility
+  pub favorites: Option<f64>,
+  pub followers: Option<f64>,
+  pub unique_subscriptions: Option<f64>,
+  pub unique_favorites: Option<f64>,
+  pub unique_followers: Option<f64>,
+  pub unique_website_views: Option<f64>,
+  pub seconds_played: Option<f64>, // Over the last `playtime_stats_days` days
+  pub playtime_sessions: Option<f64>,
+  pub comments: Option<f64>,
+}
+
+// One page of workshop query results
+#[napi(object)]
+pub struct WorkshopItemPage {
+  pub items: Vec<WorkshopItemDetails>,
+  pub total_results: u32,
+  pub returned_results: u32,
+  pub next_cursor: Option<u32>, // Page to pass back in to get the next set of results
+}
+
+// Result of advancing a WorkshopItemIterator, shaped like a JS IteratorResult
+#[napi(object)]
+pub struct WorkshopItemIteratorResult {
+  pub done: bool,
+  pub value: Option<WorkshopItemDetails>,
+}
+
+// Result of looking up one item in a batch
+#[napi(object)]
+pub struct WorkshopItemLookup {
+  pub item_id: f64,
+  pub details: Option<WorkshopItemDetails>, // Still set for banned items, alongside `error`
+  pub error: Option<String>,
+}
+
+// Workshop key-value tag
+#[napi(object)]
+#[derive(Clone)]
+pub struct WorkshopKeyValueTag {
+  pub key: String,
+  pub value: String,
+}
+
+// Filters applied to any workshop query before it is sent
+#[napi(object)]
+#[derive(Default)]
+pub struct WorkshopQueryOptions {
+  pub required_tags: Option<Vec<String>>,
+  pub match_any_tag: Option<bool>, // false (default) requires every tag in required_tags
+  pub excluded_tags: Option<Vec<String>>,
+  pub required_key_value_tags: Option<Vec<WorkshopKeyValueTag>>,
+  pub search_text: Option<String>,
+  pub ranked_by_trend_days: Option<u32>,
+  pub language: Option<String>,
+  pub cache_max_age_seconds: Option<u32>,
+  pub include_metadata: Option<bool>,
+  pub include_key_value_tags: Option<bool>,
+  pub include_additional_previews: Option<bool>,
+  pub include_children: Option<bool>,
+  pub include_statistics: Option<bool>,
+  pub playtime_stats_days: Option<u32>, // Window for the playtime statistics, defaults to 30
+}
+
+impl WorkshopQueryOptions {
+    // Whether any of the data that needs a separate details request was asked for
+    pub fn wants_extras(&self) -> bool {
+        self.include_metadata.unwrap_or(false)
+            || self.include_key_value_tags.unwrap_or(false)
+            || self.include_additional_previews.unwrap_or(false)
+    }
 }
 
 #[napi]
@@ -33,6 +155,7 @@ pub fn item_state_to_string(state: WorkshopItemStateEnum) -> &'static str {
     WorkshopItemStateEnum::NeedsUpdate => "NeedsUpdate",
     WorkshopItemStateEnum::Downloading => "Downloading",
     WorkshopItemStateEnum::DownloadPending => "DownloadPending",
+    WorkshopItemStateEnum::DisabledLocally => "DisabledLocally",
   }
 }
 
@@ -44,6 +167,17 @@ pub enum WorkshopItemVisibility {
   Unlisted = 3,
 }
 
+impl WorkshopItemVisibility {
+    pub const fn to_raw_visibility(&self) -> sys::ERemoteStoragePublishedFileVisibility {
+        match *self {
+            WorkshopItemVisibility::Public => sys::ERemoteStoragePublishedFileVisibility::k_ERemoteStoragePublishedFileVisibilityPublic,
+            WorkshopItemVisibility::FriendsOnly => sys::ERemoteStoragePublishedFileVisibility::k_ERemoteStoragePublishedFileVisibilityFriendsOnly,
+            WorkshopItemVisibility::Private => sys::ERemoteStoragePublishedFileVisibility::k_ERemoteStoragePublishedFileVisibilityPrivate,
+            WorkshopItemVisibility::Unlisted => sys::ERemoteStoragePublishedFileVisibility::k_ERemoteStoragePublishedFileVisibilityUnlisted,
+        }
+    }
+}
+
 // Workshop file type
 #[napi]
 pub enum WorkshopFileType {
@@ -112,6 +246,32 @@ pub enum WorkshopQueryType {
   RankedByLifetimePlaytimeSessions = 18,
 }
 
+impl WorkshopQueryType {
+    pub const fn to_ugc_query_type(&self) -> UGCQueryType {
+        match *self {
+            WorkshopQueryType::RankedByVote => UGCQueryType::RankedByVote,
+            WorkshopQueryType::RankedByPublicationDate => UGCQueryType::RankedByPublicationDate,
+            WorkshopQueryType::AcceptedForGameRankedByAcceptanceDate => UGCQueryType::AcceptedForGameRankedByAcceptanceDate,
+            WorkshopQueryType::RankedByTrend => UGCQueryType::RankedByTrend,
+            WorkshopQueryType::FavoritedByFriendsRankedByPublicationDate => UGCQueryType::FavoritedByFriendsRankedByPublicationDate,
+            WorkshopQueryType::CreatedByFriendsRankedByPublicationDate => UGCQueryType::CreatedByFriendsRankedByPublicationDate,
+            WorkshopQueryType::RankedByNumTimesReported => UGCQueryType::RankedByNumTimesReported,
+            WorkshopQueryType::CreatedByFollowedUsersRankedByPublicationDate => UGCQueryType::CreatedByFollowedUsersRankedByPublicationDate,
+            WorkshopQueryType::NotYetRated => UGCQueryType::NotYetRated,
+            WorkshopQueryType::RankedByTotalVotesAsc => UGCQueryType::RankedByTotalVotesAsc,
+            WorkshopQueryType::RankedByVotesUp => UGCQueryType::RankedByVotesUp,
+            WorkshopQueryType::RankedByTextSearch => UGCQueryType::RankedByTextSearch,
+            WorkshopQueryType::RankedByTotalUniqueSubscriptions => UGCQueryType::RankedByTotalUniqueSubscriptions,
+            WorkshopQueryType::RankedByPlaytimeTrend => UGCQueryType::RankedByPlaytimeTrend,
+            WorkshopQueryType::RankedByTotalPlaytime => UGCQueryType::RankedByTotalPlaytime,
+            WorkshopQueryType::RankedByAveragePlaytimeTrend => UGCQueryType::RankedByAveragePlaytimeTrend,
+            WorkshopQueryType::RankedByLifetimeAveragePlaytime => UGCQueryType::RankedByLifetimeAveragePlaytime,
+            WorkshopQueryType::RankedByPlaytimeSessionsTrend => UGCQueryType::RankedByPlaytimeSessionsTrend,
+            WorkshopQueryType::RankedByLifetimePlaytimeSessions => UGCQueryType::RankedByLifetimePlaytimeSessions,
+        }
+    }
+}
+
 // Workshop UGC types
 #[napi]
 pub enum WorkshopUGCType {
@@ -218,6 +378,69 @@ pub enum WorkshopItemStateEnum {
   NeedsUpdate = 8,
   Downloading = 16,
   DownloadPending = 32,
+  DisabledLocally = 64,
+}
+
+// Raw EItemState bits. steamworks-rs' ItemState drops k_EItemStateDisabledLocally,
+// so states are read straight from ISteamUGC::GetItemState instead.
+const ITEM_STATE_SUBSCRIBED: u32 = 1;
+const ITEM_STATE_LEGACY_ITEM: u32 = 2;
+const ITEM_STATE_INSTALLED: u32 = 4;
+const ITEM_STATE_NEEDS_UPDATE: u32 = 8;
+const ITEM_STATE_DOWNLOADING: u32 = 16;
+const ITEM_STATE_DOWNLOAD_PENDING: u32 = 32;
+const ITEM_STATE_DISABLED_LOCALLY: u32 = 64;
+
+impl WorkshopItemStateEnum {
+    pub fn from_raw_state(state: u32) -> Vec<Self> {
+        let flags = [
+            (ITEM_STATE_SUBSCRIBED, WorkshopItemStateEnum::Subscribed),
+            (ITEM_STATE_LEGACY_ITEM, WorkshopItemStateEnum::LegacyItem),
+            (ITEM_STATE_INSTALLED, WorkshopItemStateEnum::Installed),
+            (ITEM_STATE_NEEDS_UPDATE, WorkshopItemStateEnum::NeedsUpdate),
+            (ITEM_STATE_DOWNLOADING, WorkshopItemStateEnum::Downloading),
+            (ITEM_STATE_DOWNLOAD_PENDING, WorkshopItemStateEnum::DownloadPending),
+            (ITEM_STATE_DISABLED_LOCALLY, WorkshopItemStateEnum::DisabledLocally),
+        ];
+        let mut states: Vec<Self> = flags
+            .into_iter()
+            .filter(|(bit, _)| state & bit != 0)
+            .map(|(_, flag)| flag)
+            .collect();
+
+        // If no states are found, add None
+        if states.is_empty() {
+            states.push(WorkshopItemStateEnum::None);
+        }
+
+        states
+    }
+}
+
+// Workshop item state, one boolean per flag
+#[napi(object)]
+pub struct ItemStateFlags {
+  pub subscribed: bool,
+  pub legacy: bool,
+  pub installed: bool,
+  pub needs_update: bool,
+  pub downloading: bool,
+  pub download_pending: bool,
+  pub disabled_locally: bool,
+}
+
+impl ItemStateFlags {
+    pub fn from_raw_state(state: u32) -> Self {
+        ItemStateFlags {
+            subscribed: state & ITEM_STATE_SUBSCRIBED != 0,
+            legacy: state & ITEM_STATE_LEGACY_ITEM != 0,
+            installed: state & ITEM_STATE_INSTALLED != 0,
+            needs_update: state & ITEM_STATE_NEEDS_UPDATE != 0,
+            downloading: state & ITEM_STATE_DOWNLOADING != 0,
+            download_pending: state & ITEM_STATE_DOWNLOAD_PENDING != 0,
+            disabled_locally: state & ITEM_STATE_DISABLED_LOCALLY != 0,
+        }
+    }
 }
 
 // Workshop item update details
@@ -229,7 +452,22 @@ pub struct WorkshopItemUpdateDetails {
   pub preview_path: Option<String>,
   pub content_path: Option<String>,
   pub tags: Option<Vec<String>>,
-  pub visibility: Option<u32>, // 0=Public, 1=FriendsOnly, 2=Private
+  pub visibility: Option<WorkshopItemVisibility>,
+  pub add_preview_files: Option<Vec<String>>,
+  pub add_preview_videos: Option<Vec<String>>, // YouTube video ids
+  pub replace_previews: Option<Vec<WorkshopPreviewReplacement>>,
+  pub remove_previews: Option<Vec<u32>>, // Indices as returned in WorkshopItemPreview::index
+  pub metadata: Option<String>,            // Up to 5000 bytes, replaces the existing blob
+  pub add_key_value_tags: Option<Vec<WorkshopKeyValueTag>>,
+  pub remove_key_value_tags: Option<Vec<String>>, // Removes every tag with the given key
+}
+
+// Replaces the additional preview at `index` with an image file or a YouTube video
+#[napi(object)]
+pub struct WorkshopPreviewReplacement {
+  pub index: u32,
+  pub file: Option<String>,
+  pub video_id: Option<String>,
 }
 
 // Workshop item creation result
@@ -239,6 +477,44 @@ pub struct WorkshopItemCreationResult {
   pub needs_to_accept_agreement: bool,
 }
 
+// Items sorted so dependencies load first, plus anything that got in the way
+#[napi(object)]
+pub struct WorkshopLoadOrder {
+  pub order: Vec<f64>,
+  pub cycles: Vec<Vec<f64>>,
+  pub missing: Vec<WorkshopMissingDependency>,
+}
+
+// A dependency that isn't among the resolved items, or an item Steam couldn't find
+#[napi(object)]
+pub struct WorkshopMissingDependency {
+  pub item_id: f64,
+  pub required_by: Vec<f64>,
+}
+
+// Whether an overlay page could be shown, along with the url it points at
+#[napi(object)]
+pub struct WorkshopOverlayResult {
+  pub overlay_enabled: bool,
+  pub url: String,
+}
+
+// The current user's vote on a workshop item
+#[napi(object)]
+pub struct WorkshopItemVote {
+  pub voted_up: bool,
+  pub voted_down: bool,
+  pub vote_skipped: bool,
+}
+
+// Payload of the workshop item-installed and download-result events
+#[napi(object)]
+pub struct WorkshopItemEvent {
+  pub app_id: u32,
+  pub item_id: f64,
+  pub result: i32, // Steam EResult, 1 = OK
+}
+
 // Workshop item installation info
 #[napi(object)]
 pub struct WorkshopItemInstallInfo {
@@ -253,3 +529,71 @@ pub struct DownloadInfo {
   pub current: f64, // Using f64 instead of u64 for JS compatibility
   pub total: f64,   // Using f64 instead of u64 for JS compatibility
 }
+
+// Workshop item update status
+#[napi]
+pub enum WorkshopItemUpdateStatus {
+  Invalid = 0,
+  PreparingConfig = 1,
+  PreparingContent = 2,
+  UploadingContent = 3,
+  UploadingPreviewFile = 4,
+  CommittingChanges = 5,
+}
+
+impl WorkshopItemUpdateStatus {
+    pub fn from_raw(status: sys::EItemUpdateStatus) -> Self {
+        match status as u32 {
+            1 => WorkshopItemUpdateStatus::PreparingConfig,
+            2 => WorkshopItemUpdateStatus::PreparingContent,
+            3 => WorkshopItemUpdateStatus::UploadingContent,
+            4 => WorkshopItemUpdateStatus::UploadingPreviewFile,
+            5 => WorkshopItemUpdateStatus::CommittingChanges,
+            _ => WorkshopItemUpdateStatus::Invalid,
+        }
+    }
+}
+
+// Workshop item update progress
+#[napi(object)]
+pub struct WorkshopItemUpdateProgress {
+  pub status: WorkshopItemUpdateStatus,
+  pub bytes_processed: f64, // Using f64 instead of u64 for JS compatibility
+  pub bytes_total: f64,     // Using f64 instead of u64 for JS compatibility
+}
+
+// Options for UGCClient::ensure_installed
+#[napi(object, object_to_js = false)]
+pub struct EnsureInstalledOptions {
+  pub high_priority: Option<bool>, // Defaults to true
+  pub timeout_ms: Option<u32>,
+  #[napi(ts_type = "(value: DownloadInfo) => any")]
+  pub on_progress: Option<ThreadsafeFunction<DownloadInfo, ErrorStrategy::Fatal>>,
+}
+
+// Snapshot of one subscribed item, as recorded in a subscription manifest
+#[napi(object)]
+#[derive(Clone)]
+pub struct WorkshopManifestItem {
+  pub item_id: f64,
+  pub states: Vec<WorkshopItemStateEnum>,
+  pub folder: Option<String>,
+  pub size_on_disk: Option<f64>,
+  pub timestamp: Option<u32>,
+}
+
+// Snapshot of every subscribed item, meant to be saved as JSON between runs
+#[napi(object)]
+pub struct WorkshopSubscriptionManifest {
+  pub items: Vec<WorkshopManifestItem>,
+}
+
+// Changes between a saved subscription manifest and the current one
+#[napi(object)]
+pub struct WorkshopManifestDiff {
+  pub added: Vec<WorkshopManifestItem>,
+  pub removed: Vec<f64>,
+  pub updated: Vec<WorkshopManifestItem>,
+  pub broken: Vec<WorkshopManifestItem>, // Subscribed but not installed, or the install folder is gone
+  pub manifest: WorkshopSubscriptionManifest,
+}
@@ -0,0 +1,116 @@
+use napi::bindgen_prelude::*;
+use napi::{Env, Error, Result};
+use std::sync::mpsc;
+use std::sync::Arc;
+use steamworks_sys as sys;
+
+use super::types::WorkshopItemVote;
+use crate::errors::steam_error_to_napi;
+use crate::steam_api;
+
+// Votes are kept server side, so there is nothing to fall back on while offline
+fn check_logged_on(client: &steamworks::Client) -> Result<()> {
+  if client.user().logged_on() {
+    Ok(())
+  } else {
+    Err(steam_error_to_napi(steamworks::SteamError::NoConnection))
+  }
+}
+
+/// Async Task for voting a workshop item up or down
+pub struct WorkshopSetItemVoteTask {
+  pub client: Arc<steamworks::Client>,
+  pub item_id: f64,
+  pub vote_up: bool,
+}
+
+impl WorkshopSetItemVoteTask {
+  fn set_item_vote(&self) -> Result<()> {
+    check_logged_on(&self.client)?;
+
+    let (tx, rx) = mpsc::channel();
+    let call = unsafe {
+      sys::SteamAPI_ISteamUGC_SetUserItemVote(steam_api::call_ugc(), self.item_id as u64, self.vote_up)
+    };
+    steam_api::register_call_result::<sys::SetUserItemVoteResult_t>(call, move |result| {
+      let _ = tx.send(result);
+    });
+    match rx.recv() {
+      Ok(Ok(result)) => steam_api::check_result(result.m_eResult).map_err(steam_error_to_napi),
+      Ok(Err(e)) => Err(Error::from_reason(e)),
+      Err(e) => Err(Error::from_reason(format!(
+        r"Steamworks: Failed to receive vote result: {:?}",
+        e
+      ))),
+    }
+  }
+}
+
+#[napi]
+impl Task for WorkshopSetItemVoteTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.set_item_vote()
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}
+
+/// Async Task for looking up the current user's vote on a workshop item
+pub struct WorkshopGetItemVoteTask {
+  pub client: Arc<steamworks::Client>,
+  pub item_id: f64,
+}
+
+impl WorkshopGetItemVoteTask {
+  fn get_item_vote(&self) -> Result<WorkshopItemVote> {
+    check_logged_on(&self.client)?;
+
+    let (tx, rx) = mpsc::channel();
+    let call = unsafe { sys::SteamAPI_ISteamUGC_GetUserItemVote(steam_api::call_ugc(), self.item_id as u64) };
+    steam_api::register_call_result::<sys::GetUserItemVoteResult_t>(call, move |result| {
+      let _ = tx.send(result);
+    });
+    match rx.recv() {
+      Ok(Ok(result)) => {
+        steam_api::check_result(result.m_eResult).map_err(steam_error_to_napi)?;
+        Ok(WorkshopItemVote {
+          voted_up: result.m_bVotedUp,
+          voted_down: result.m_bVotedDown,
+          vote_skipped: result.m_bVoteSkipped,
+        })
+      }
+      Ok(Err(e)) => Err(Error::from_reason(e)),
+      Err(e) => Err(Error::from_reason(format!(
+        r"Steamworks: Failed to receive vote: {:?}",
+        e
+      ))),
+    }
+  }
+}
+
+#[napi]
+impl Task for WorkshopGetItemVoteTask {
+  type Output = WorkshopItemVote;
+  type JsValue = WorkshopItemVote;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.get_item_vote()
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+
+  fn reject(&mut self, _: Env, error: Error) -> Result<Self::JsValue> {
+    Err(error)
+  }
+}